@@ -0,0 +1 @@
+util::main!(day1::part_1);
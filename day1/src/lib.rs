@@ -1,11 +1,19 @@
-use std::str::FromStr;
+pub mod part_1;
+pub mod part_2;
+
+use util::compare::Rng;
+use util::math::wrap;
+use util::scan::AocParse;
 
 const DEFAULT_MIN: u64 = 0;
 const DEFAULT_MAX: u64 = 99;
 
 /// A turn on a [`Dial`]. `L(99)` means a turn to the left 99 places.
+#[derive(AocParse)]
 pub enum Turn {
+    #[aoc(pattern = "L{}")]
     L(u16),
+    #[aoc(pattern = "R{}")]
     R(u16),
 }
 
@@ -18,30 +26,6 @@ impl std::fmt::Display for Turn {
     }
 }
 
-/// Error type for parsing [`Turn`]s.
-#[derive(thiserror::Error, Debug)]
-pub enum ParseTurnError {
-    /// Indicates that the distance component of a turn couldn't be parsed.
-    #[error(transparent)]
-    InvalidDistance(#[from] std::num::ParseIntError),
-
-    /// Indicates that a turn's direction is not recognized.
-    #[error("invalid direction; expected 'L' or 'R', got '{0}'")]
-    InvalidDirection(String),
-}
-
-impl FromStr for Turn {
-    type Err = ParseTurnError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.split_at(1) {
-            ("L", distance) => Ok(Turn::L(distance.parse()?)),
-            ("R", distance) => Ok(Turn::R(distance.parse()?)),
-            (direction, _distance) => Err(ParseTurnError::InvalidDirection(direction.into())),
-        }
-    }
-}
-
 /// Stats about dial position.
 #[derive(Default, Debug)]
 pub struct DialStats {
@@ -87,69 +71,58 @@ impl Dial {
     pub fn turn(&mut self, turn: Turn) -> u64 {
         let old_current = self.current;
         match turn {
-            Turn::R(distance) => self.add(distance.into()),
-            Turn::L(distance) => self.sub(distance.into()),
+            Turn::R(distance) => self.rotate(distance.into()),
+            Turn::L(distance) => self.rotate(-i64::from(distance)),
         };
         tracing::debug!("Pos {} + {} = Pos {}", old_current, turn, self.current);
         tracing::debug!("{:?}", self.stats);
         self.current
     }
 
-    #[inline]
-    fn rollover(&self, i: u64) -> (u64, u64) {
-        let range = self.max - self.min + 1;
-        let full_rotations = i / range;
-        let remainder = i % range;
-        tracing::trace!(
-            "Distance {i} is {full_rotations} full rotations and {remainder} additional ticks"
-        );
-        (full_rotations, remainder)
-    }
-
-    fn add(&mut self, i: u64) -> u64 {
-        let (full_rotations, remainder) = self.rollover(i);
-        let space = self.max - self.current;
-
-        // If we turn 350 ticks, no matter what we will touch min 3 times
-        self.stats.touched_min += full_rotations;
-
-        if remainder <= space {
-            self.current += remainder;
-        } else {
-            tracing::debug!("Overflow, will definitely pass {}", self.min);
-            self.stats.touched_min += 1;
-            self.current = self.min + (remainder - space - 1);
+    /// Apply a [`Turn`] one tick at a time. Much slower than [`Dial::turn`], but obviously
+    /// correct, so it's kept around as a reference implementation.
+    pub fn turn_by_ticks(&mut self, turn: Turn) -> u64 {
+        let (distance, step_right) = match turn {
+            Turn::R(distance) => (distance, true),
+            Turn::L(distance) => (distance, false),
+        };
+        for _ in 0..distance {
+            self.current = match (step_right, self.current) {
+                (true, current) if current == self.max => self.min,
+                (true, current) => current + 1,
+                (false, current) if current == self.min => self.max,
+                (false, current) => current - 1,
+            };
+            if self.current == self.min {
+                self.stats.touched_min += 1;
+            }
         }
 
         if self.current == self.min {
-            tracing::debug!("Landed on {}", self.min);
             self.stats.landed_on_min += 1;
         }
-
         self.current
     }
 
-    fn sub(&mut self, i: u64) -> u64 {
-        let (full_rotations, remainder) = self.rollover(i);
-        let space = self.current - self.min;
-
-        // If we turn 350 ticks, no matter what we will touch min 3 times
-        self.stats.touched_min += full_rotations;
-
-        // If we land on the min (remainder == space) or overflow (remainder > space) we
-        // will touch min once. However, if we are starting from min, we've already counted
-        // this instance so we should skip it.
-        if remainder >= space && self.current != self.min {
-            self.stats.touched_min += 1;
-        }
-
-        if remainder <= space {
-            self.current -= remainder;
+    /// Turn the dial `distance` ticks, to the right if positive or to the left if negative.
+    fn rotate(&mut self, distance: i64) -> u64 {
+        let range = i128::from(self.min)..i128::from(self.max) + 1;
+        let len = range.end - range.start;
+        let start = i128::from(self.current);
+        let end = start + i128::from(distance);
+
+        // Unwrapped, the dial is at `min` every `len` ticks, so the number of times it touches
+        // `min` is how many of those positions it passes, not counting where it starts.
+        let laps = |position: i128| (position - range.start).div_euclid(len);
+        let touched = if distance >= 0 {
+            laps(end) - laps(start)
         } else {
-            tracing::debug!("Underflow");
-            self.current = self.max - (remainder - space - 1);
-        }
+            laps(start - 1) - laps(end - 1)
+        };
+        tracing::trace!("Distance {distance} touches {} {touched} times", self.min);
+        self.stats.touched_min += touched as u64;
 
+        self.current = wrap(end, range) as u64;
         if self.current == self.min {
             tracing::debug!("Landed on {}", self.min);
             self.stats.landed_on_min += 1;
@@ -158,3 +131,35 @@ impl Dial {
         self.current
     }
 }
+
+/// Generate a random list of [`Turn`]s, one per line, for comparing solutions.
+pub fn generate_turns(rng: &mut Rng) -> String {
+    let turns = rng.range(1, 200);
+    (0..turns)
+        .map(|_| {
+            let distance = rng.range(0, 1000) as u16;
+            if rng.one_in(2) {
+                format!("{}\n", Turn::L(distance))
+            } else {
+                format!("{}\n", Turn::R(distance))
+            }
+        })
+        .collect()
+}
+
+util::aoc_tests! {
+    example: "\
+L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82
+",
+    part_1: "3",
+    part_2: "6",
+}
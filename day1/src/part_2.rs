@@ -1,15 +1,14 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 
-use day1::*;
+use crate::*;
 
 const STARTS_AT: u64 = 50;
 
-fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
+pub fn solve(input: &[u8]) -> anyhow::Result<String> {
     let mut dial = Dial::new(STARTS_AT);
 
     tracing::debug!("Starting at {STARTS_AT}");
-    for line in reader.lines() {
+    for line in input.lines() {
         dial.turn(line?.parse()?);
     }
 
@@ -17,4 +16,13 @@ fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
     Ok(dial.stats.touched_min.to_string())
 }
 
-util::main!();
+/// Reference implementation which turns the dial one tick at a time.
+fn solve_by_ticks(input: &[u8]) -> anyhow::Result<String> {
+    let mut dial = Dial::new(STARTS_AT);
+    for line in input.lines() {
+        dial.turn_by_ticks(line?.parse()?);
+    }
+    Ok(dial.stats.touched_min.to_string())
+}
+
+util::solution!(alternatives: [ticks => solve_by_ticks], generator: generate_turns);
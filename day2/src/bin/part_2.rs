@@ -0,0 +1 @@
+util::main!(day2::part_2);
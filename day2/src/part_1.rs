@@ -1,15 +1,12 @@
-use std::fs::File;
-use std::io::{BufReader, prelude::*};
 use std::ops::RangeInclusive;
 
-use day2::*;
+use crate::*;
 use util::range::ParseRange;
 
-fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
+pub fn solve(input: &[u8]) -> anyhow::Result<String> {
     let mut sum = 0;
-    for item in reader.split(b',') {
-        let item = item?;
-        let range = str::from_utf8(&item)?;
+    for item in input.split(|&byte| byte == b',') {
+        let range = str::from_utf8(item)?;
         tracing::debug!(">>> Processing range {:?}", range);
 
         let id_range = RangeInclusive::<u64>::parse_range(range)?;
@@ -28,4 +25,4 @@ fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
     Ok(sum.to_string())
 }
 
-util::main!();
+util::solution!();
@@ -1,13 +1,11 @@
+pub mod part_1;
+pub mod part_2;
+
+use util::digits::{self, DigitError};
+
 pub type Battery = u8;
 pub type BatteryBank<'a> = &'a [Battery];
 
-/// "Parse" an ASCII character by subtracting the ASCII value of '0'.
-///
-/// While we're at it, convert to u64 so our math operations don't overflow.
-fn unchar(ascii_char: Battery) -> u64 {
-    (ascii_char - 48).into()
-}
-
 /// Errors that occur while processing a [`BatteryBank`].
 #[derive(thiserror::Error, Debug)]
 pub enum BankError {
@@ -15,9 +13,13 @@ pub enum BankError {
     #[error("no batteries left in bank")]
     NoBatteries,
 
-    /// Indicates that the number of batteries you are enabling is outside the [`u32`] range.
+    /// Indicates that a battery isn't labeled with a digit.
     #[error(transparent)]
-    NumberError(#[from] std::num::TryFromIntError),
+    DigitError(#[from] DigitError),
+
+    /// Indicates that the joltage is too large for a [`u64`].
+    #[error("joltage overflowed")]
+    Overflow,
 }
 
 /// Find the maximum voltage possible for `bank` by turning on `enable_limit` batteries.
@@ -35,8 +37,8 @@ pub fn max_joltage(bank: BatteryBank, enable_limit: usize) -> Result<u64, BankEr
             .max_by_key(|&(_idx, val)| val)
             .ok_or(BankError::NoBatteries)?;
 
-        let pow = u32::try_from(enable_limit - i)?;
-        bank_joltage += 10_u64.pow(pow) * unchar(*juiciest_battery);
+        let joltage = digits::parse_digit(char::from(*juiciest_battery), 10, idx)?;
+        bank_joltage = digits::concat(bank_joltage, joltage, 10).ok_or(BankError::Overflow)?;
 
         tracing::trace!(
             "> {}th juiciest battery is {} at {}",
@@ -50,3 +52,14 @@ pub fn max_joltage(bank: BatteryBank, enable_limit: usize) -> Result<u64, BankEr
 
     Ok(bank_joltage)
 }
+
+util::aoc_tests! {
+    example: "\
+987654321111111
+811111111111119
+234234234234278
+818181911112111
+",
+    part_1: "357",
+    part_2: "3121910778619",
+}
@@ -0,0 +1 @@
+util::main!(day3::part_1, day3::part_2);
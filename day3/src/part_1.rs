@@ -1,15 +1,14 @@
-use std::fs::File;
-use std::io::{BufReader, prelude::*};
+use std::io::prelude::*;
 
 const BATTERIES_PER_BANK: usize = 2;
 
-fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
+pub fn solve(input: &[u8]) -> anyhow::Result<String> {
     let mut total_joltage = 0;
-    for bank in reader.lines() {
+    for bank in input.lines() {
         let bank = bank?;
         tracing::debug!("Processing bank: {}", bank);
 
-        let bank_joltage = day3::max_joltage(bank.as_bytes(), BATTERIES_PER_BANK)?;
+        let bank_joltage = crate::max_joltage(bank.as_bytes(), BATTERIES_PER_BANK)?;
 
         tracing::debug!("> Bank joltage: {bank_joltage}");
         total_joltage += bank_joltage;
@@ -19,4 +18,4 @@ fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
     Ok(total_joltage.to_string())
 }
 
-util::main!();
+util::solution!();
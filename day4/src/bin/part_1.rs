@@ -0,0 +1 @@
+util::main!(day4::part_1);
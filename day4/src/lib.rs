@@ -1,2 +1,20 @@
-pub mod grid;
 pub mod paper_storage;
+pub mod part_1;
+pub mod part_2;
+
+util::aoc_tests! {
+    example: "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+",
+    part_1: "13",
+    part_2: "43",
+}
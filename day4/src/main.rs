@@ -0,0 +1 @@
+util::main!(day4::part_1, day4::part_2);
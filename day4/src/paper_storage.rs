@@ -1,6 +1,7 @@
 use std::io::{BufRead, Lines};
 
-use crate::grid::{Adjacency, GrowableGrid};
+use util::automaton::{Automaton, Update};
+use util::grid::{Grid, GridError};
 
 /// A roll is unreachable when `UNREACHABLE_THRESHOLD` rolls are adjacent to it.
 const UNREACHABLE_THRESHOLD: u8 = 4;
@@ -10,102 +11,113 @@ pub enum PaperStorageError {
     #[error("no rows in paper inventory")]
     EmptyInventory,
 
+    #[error(transparent)]
+    GridError(#[from] GridError),
+
     #[error(transparent)]
     IoError(#[from] std::io::Error),
 }
 
 pub struct PaperStorage {
-    grid: GrowableGrid,
+    /// Each cell holds the number of rolls adjacent to the roll there, or `None` if there is no
+    /// roll.
+    grid: Grid<Option<u8>>,
 }
 
 impl PaperStorage {
     /// Import a paper inventory from an iterable of strings.
+    #[tracing::instrument(skip_all)]
     pub fn import<T: BufRead>(lines: Lines<T>) -> Result<PaperStorage, PaperStorageError> {
-        let mut lines = lines.peekable();
-        let cols = match lines.peek() {
-            Some(Ok(line)) => Ok(line.len()),
-            _ => Err(PaperStorageError::EmptyInventory),
-        }?;
-        let mut grid = GrowableGrid::new(cols);
-
-        let mut current_row: usize = 0;
-        for line in lines {
-            let line = line?;
-            tracing::trace!("{:?}", line);
-            for (col, character) in line.chars().enumerate() {
-                // If there is no paper roll here, do nothing.
-                if character != '@' {
-                    continue;
-                }
-
-                // Otherwise, we want to look at all previously-populated cells,
-                // - Count how many rolls we find, because that's our initial adjacency count
-                // - For each of those rolls, increment their adjacency count to include us
-                // Later cells will update our count if they are adjacent to us so we don't have to
-                // look to our right or below us.
-                let mut adjacent_rolls = 0;
-                for adj in [
-                    Adjacency::Left,
-                    Adjacency::TopLeft,
-                    Adjacency::Top,
-                    Adjacency::TopRight,
-                ] {
-                    if let Some(adjacent) = grid.get_adjacent(current_row, col, adj) {
-                        adjacent_rolls += 1;
-                        *adjacent += 1;
-                    }
-                }
-
-                grid.put(current_row, col, Some(adjacent_rolls));
-            }
-            current_row += 1;
+        let mut lines = lines.collect::<Result<Vec<String>, _>>()?;
+        // Inputs often end with a blank line, which would otherwise be a ragged row.
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+        let rolls = Grid::from_lines(lines, |c| Some(c == '@'))?;
+        if rolls.is_empty() {
+            return Err(PaperStorageError::EmptyInventory);
+        }
+
+        let grid = rolls.map(|_| None);
+        let mut paper_storage = PaperStorage { grid };
+        for point in rolls.find_all(&true) {
+            let adjacent_rolls = rolls.neighbors8(point).filter(|p| rolls[*p]).count();
+            paper_storage.grid[point] = Some(adjacent_rolls as u8);
         }
 
-        Ok(PaperStorage { grid })
+        Ok(paper_storage)
+    }
+
+    /// How many rolls of paper there are.
+    pub fn rolls(&self) -> usize {
+        self.grid.iter().filter(|(_, cell)| cell.is_some()).count()
+    }
+
+    /// An [`Automaton`] which applies [`remove_reachable`] to every roll in each step.
+    fn remover(&mut self) -> Automaton<'_, Option<u8>, Rule> {
+        Automaton::new(&mut self.grid, remove_reachable as Rule)
+            .with_update(Update::Worklist)
+            .on_step(|grid, step| {
+                tracing::debug!(
+                    "Generation {}: {} cells changed\n{}",
+                    step.generation,
+                    step.changed,
+                    grid.display(cell_char)
+                );
+            })
     }
 
     /// Remove all reachable rolls of paper. Returns the number of rolls removed.
+    #[tracing::instrument(skip_all)]
     pub fn remove_reachable_rolls(&mut self) -> usize {
-        // Identify all of the reachable rolls
-        let mut reachable = vec![];
-        for (coords, val) in self.grid.iter() {
-            match val {
-                Some(adjacent_rolls) if *adjacent_rolls < UNREACHABLE_THRESHOLD => {
-                    reachable.push(coords);
-                }
-                _ => {}
-            }
-        }
+        let rolls = self.rolls();
+        self.remover().step();
+        rolls - self.rolls()
+    }
+
+    /// Remove reachable rolls of paper until none are left. Returns the number of rolls removed.
+    #[tracing::instrument(skip_all)]
+    pub fn remove_all_reachable_rolls(&mut self) -> usize {
+        let rolls = self.rolls();
+        self.remover().run_until_stable();
+        rolls - self.rolls()
+    }
+}
+
+type Rule = fn(&Option<u8>, &[&Option<u8>]) -> Option<u8>;
 
-        // Remove each reachable roll and remove it from the adjacency count of each surrounding
-        // roll
-        for (x, y) in reachable.iter() {
-            self.grid.put(*x, *y, None);
-            for direction in [
-                Adjacency::TopLeft,
-                Adjacency::Top,
-                Adjacency::TopRight,
-                Adjacency::Right,
-                Adjacency::BottomRight,
-                Adjacency::Bottom,
-                Adjacency::BottomLeft,
-                Adjacency::Left,
-            ] {
-                if let Some(adjacent_rolls) = self.grid.get_adjacent(*x, *y, direction) {
-                    *adjacent_rolls -= 1;
-                    tracing::trace!(
-                        "Decremented ({x}, {y}) {direction:?}, new value {adjacent_rolls}"
-                    );
-                }
-            }
+/// Remove a roll of paper if it's reachable. Otherwise, update its count of adjacent rolls to
+/// leave out the ones that are about to be removed.
+fn remove_reachable(cell: &Option<u8>, neighbors: &[&Option<u8>]) -> Option<u8> {
+    match cell {
+        Some(_) if is_reachable(cell) => None,
+        Some(_) => {
+            let remaining = neighbors
+                .iter()
+                .filter(|neighbor| neighbor.is_some() && !is_reachable(neighbor))
+                .count();
+            Some(remaining as u8)
         }
+        None => None,
+    }
+}
+
+/// Whether a cell holds a roll of paper with fewer than [`UNREACHABLE_THRESHOLD`] adjacent rolls.
+fn is_reachable(cell: &Option<u8>) -> bool {
+    cell.is_some_and(|adjacent_rolls| adjacent_rolls < UNREACHABLE_THRESHOLD)
+}
 
-        reachable.len()
+/// Render a cell as its number of adjacent rolls, or `.` if there is no roll.
+fn cell_char(cell: &Option<u8>) -> char {
+    match cell {
+        Some(adjacent_rolls) => char::from_digit(u32::from(*adjacent_rolls), 10).unwrap_or('?'),
+        None => '.',
     }
 }
 
 impl std::fmt::Display for PaperStorage {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "PaperStorage: {}", self.grid)
+        let grid = self.grid.display(cell_char);
+        write!(f, "PaperStorage:\n{grid}")
     }
 }
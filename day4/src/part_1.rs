@@ -1,10 +1,9 @@
-use std::fs::File;
-use std::io::{BufReader, prelude::*};
+use std::io::prelude::*;
 
-use day4::paper_storage::PaperStorage;
+use crate::paper_storage::PaperStorage;
 
-fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
-    let mut paper_storage = PaperStorage::import(reader.lines())?;
+pub fn solve(input: &[u8]) -> anyhow::Result<String> {
+    let mut paper_storage = PaperStorage::import(input.lines())?;
     let accessible_rolls = paper_storage.remove_reachable_rolls();
 
     tracing::info!("There are {accessible_rolls} accessible paper rolls.");
@@ -12,4 +11,4 @@ fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
     Ok(accessible_rolls.to_string())
 }
 
-util::main!();
+util::solution!();
@@ -0,0 +1 @@
+util::main!(day5::part_1);
@@ -1,131 +1,54 @@
-use std::collections::BTreeMap;
-use std::iter::FromIterator;
+pub mod part_1;
+pub mod part_2;
 
-use util::range::{ParseRange, RangeError};
+use std::ops::RangeInclusive;
+
+use util::range::{IntervalSet, ParseRange, RangeError};
 
 pub struct Kitchen {
-    fresh_ranges: MultiRange,
+    fresh_ranges: IntervalSet<u64>,
 }
 
 impl Kitchen {
-    pub fn import_fresh_ranges(lines: impl Iterator<Item = String>) -> anyhow::Result<Kitchen> {
+    #[tracing::instrument(skip_all)]
+    pub fn import_fresh_ranges(
+        lines: impl Iterator<Item = impl AsRef<str>>,
+    ) -> anyhow::Result<Kitchen> {
         let fresh_ranges = lines
-            .map(|s| <(u64, u64) as ParseRange>::parse_range(s.as_str()))
-            .collect::<Result<MultiRange, RangeError>>()?;
+            .map(|s| <RangeInclusive<u64> as ParseRange>::parse_range(s.as_ref()))
+            .collect::<Result<IntervalSet<u64>, RangeError>>()?;
+        tracing::trace!(?fresh_ranges);
         Ok(Kitchen { fresh_ranges })
     }
 
     pub fn is_fresh(&self, ingredient: u64) -> bool {
-        self.fresh_ranges.check_point(ingredient)
+        self.fresh_ranges.contains(ingredient)
     }
 
     pub fn fresh_ingredients(&self) -> impl Iterator<Item = u64> {
-        self.fresh_ranges.iter()
-    }
-}
-
-/// A set of non-overlapping integer ranges. When a new range is inserted with
-/// [`MultiRange::insert`], the new range and each range it overlaps with are flattened into a
-/// single range.
-struct MultiRange {
-    ranges: BTreeMap<u64, u64>,
-    reverse_ranges: BTreeMap<u64, u64>,
-}
-
-impl MultiRange {
-    pub fn new() -> MultiRange {
-        MultiRange {
-            ranges: BTreeMap::new(),
-            reverse_ranges: BTreeMap::new(),
-        }
-    }
-
-    fn overlapping(&self, (start, end): (u64, u64)) -> impl Iterator<Item = (u64, u64)> {
-        let ends_after_start = self
-            .reverse_ranges
-            .range(start..=end)
-            .map(|(e, s)| (*s, *e));
-        let starts_before_end = self.ranges.range(start..=end).map(|(s, e)| (*s, *e));
-        let contains = self
-            .ranges
-            .range(..=start)
-            .filter(move |(_s, e)| **e >= end)
-            .map(|(s, e)| (*s, *e));
-
-        // TODO: Filter out duplicates
-        ends_after_start.chain(starts_before_end).chain(contains)
-
-        /* TODO Benchmark / consider getting rid of `reverse_ranges`
-        let between = move |x: &&u64| **x >= start && **x <= end;
-        self.ranges
-            .iter()
-            .filter(move |(s, e)| between(s) || between(e))
-            .map(|(s, e)| (*s, *e))
-        */
+        self.fresh_ranges.values()
     }
 
-    /// Insert a new inclusive range. Flatten overlapping ranges into a single range.
-    pub fn insert(&mut self, (start, end): (u64, u64)) {
-        // All ranges that overlap with the current one can be flattened into a single range which
-        // begins where the earliest overlapping range begins and ends where the latest overlapping
-        // range ends. Find `min_start` and `max_end` accordingly.
-        let ranges_to_merge: Vec<(u64, u64)> = self.overlapping((start, end)).collect();
-        tracing::trace!(
-            "({start}, {end}) has {} overlapping ranges: {ranges_to_merge:?}",
-            ranges_to_merge.len()
-        );
-        let mut min_start = start;
-        let mut max_end = end;
-        for (start, end) in ranges_to_merge.iter() {
-            min_start = std::cmp::min(*start, min_start);
-            max_end = std::cmp::max(*end, max_end);
-        }
-
-        tracing::trace!(
-            "Merging {} ranges into ({min_start}, {max_end})",
-            ranges_to_merge.len()
-        );
-
-        // Insert our flattened range into our maps.
-        self.ranges.insert(min_start, max_end);
-        self.reverse_ranges.insert(max_end, min_start);
-
-        // Remove the pre-flattened ranges.
-        for (start, end) in ranges_to_merge.iter() {
-            if *start != min_start {
-                tracing::trace!("Removing ({start}, {end}) from forward range list");
-                self.ranges.remove(start);
-            }
-            if *end != max_end {
-                tracing::trace!("Removing ({start}, {end}) from reverse range list");
-                self.reverse_ranges.remove(end);
-            }
-        }
-    }
-
-    /// Check whether a single point is in contained in any of this [`MultiRange`]'s ranges.
-    pub fn check_point(&self, point: u64) -> bool {
-        self.ranges
-            .iter()
-            .any(|(start, end)| point >= *start && point <= *end)
-    }
-
-    /// Iterate from lowest to highest over every value included in a range.
-    pub fn iter(&self) -> impl Iterator<Item = u64> {
-        self.ranges.iter().flat_map(|(start, end)| *start..=*end)
+    /// Count the fresh ingredients without enumerating them.
+    pub fn fresh_ingredient_count(&self) -> u128 {
+        self.fresh_ranges.len()
     }
 }
 
-impl FromIterator<(u64, u64)> for MultiRange {
-    fn from_iter<I: IntoIterator<Item = (u64, u64)>>(iter: I) -> Self {
-        let mut multirange = MultiRange::new();
-
-        for i in iter {
-            multirange.insert(i);
-            tracing::trace!(?multirange.ranges);
-            tracing::trace!(?multirange.reverse_ranges);
-        }
-
-        multirange
-    }
+util::aoc_tests! {
+    example: "\
+3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32
+",
+    part_1: "3",
+    part_2: "14",
 }
@@ -1,3 +1 @@
-fn main() {
-    println!("Hello, world!");
-}
+util::main!(day5::part_1, day5::part_2);
@@ -1,24 +1,19 @@
-use std::fs::File;
-use std::io::{BufReader, prelude::*};
+use crate::*;
+use util::input::Sections;
 
-use day5::*;
-
-fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
-    let mut lines = reader.lines();
+pub fn solve(input: &[u8]) -> anyhow::Result<String> {
+    let mut sections = Sections::new(input);
 
     tracing::debug!("Building kitchen inventory");
-    let kitchen = Kitchen::import_fresh_ranges(
-        lines
-            .by_ref()
-            .map(|s| s.expect("failed to read file"))
-            .take_while(|s| !s.is_empty()),
-    )?;
+    let kitchen = Kitchen::import_fresh_ranges(sections.next_section()?.lines())?;
 
     tracing::debug!("Counting fresh ingredients");
+    // An input with no available ingredients has no second section.
+    let available = sections.next().transpose()?.unwrap_or_default();
     let mut fresh_ingredients = 0;
-    for line in lines {
+    for line in available.lines() {
         tracing::debug!("> Checking {line:?}");
-        if kitchen.is_fresh(line?.parse::<u64>()?) {
+        if kitchen.is_fresh(line.parse::<u64>()?) {
             tracing::debug!("> > Fresh");
             fresh_ingredients += 1;
         }
@@ -28,4 +23,4 @@ fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
     Ok(fresh_ingredients.to_string())
 }
 
-util::main!();
+util::solution!();
@@ -1,23 +1,16 @@
-use std::fs::File;
-use std::io::{BufReader, prelude::*};
+use crate::*;
+use util::input::Sections;
 
-use day5::*;
-
-fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
-    let mut lines = reader.lines();
+pub fn solve(input: &[u8]) -> anyhow::Result<String> {
+    let mut sections = Sections::new(input);
 
     tracing::debug!("Building kitchen inventory");
-    let kitchen = Kitchen::import_fresh_ranges(
-        lines
-            .by_ref()
-            .map(|s| s.expect("failed to read file"))
-            .take_while(|s| !s.is_empty()),
-    )?;
+    let kitchen = Kitchen::import_fresh_ranges(sections.next_section()?.lines())?;
 
-    let total_fresh_ingredients = kitchen.fresh_ingredients().count();
+    let total_fresh_ingredients = kitchen.fresh_ingredient_count();
     tracing::info!("Total number of fresh ingredients: {total_fresh_ingredients}");
 
     Ok(total_fresh_ingredients.to_string())
 }
 
-util::main!();
+util::solution!();
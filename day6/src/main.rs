@@ -0,0 +1 @@
+util::main!(day6::part_1, day6::part_2);
@@ -1,12 +1,11 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 
-use day6::{NumberFormat, Worksheet};
+use crate::{NumberFormat, Worksheet};
 
-fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
-    Ok(Worksheet::new(reader.lines())?
+pub fn solve(input: &[u8]) -> anyhow::Result<String> {
+    Ok(Worksheet::new(input.lines())?
         .solve(NumberFormat::LeftRightTopBottom)?
         .to_string())
 }
 
-util::main!();
+util::solution!();
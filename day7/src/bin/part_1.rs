@@ -0,0 +1 @@
+util::main!(day7::part_1);
@@ -1,6 +1,10 @@
-use std::collections::HashSet;
+pub mod part_1;
+pub mod part_2;
+
 use std::io::{BufRead, Lines};
 
+use util::bitset::BitSet;
+
 #[derive(thiserror::Error, Debug)]
 pub enum ManifoldError {
     #[error("no manifold!")]
@@ -18,59 +22,43 @@ pub enum ManifoldError {
 
 #[derive(Default)]
 pub struct TachyonManifold {
-    pub beams: HashSet<usize>,
+    pub beams: BitSet,
     pub splits: u16,
 }
 
 impl TachyonManifold {
+    #[tracing::instrument(level = "trace", skip_all)]
     fn tick(&mut self, line: &str) -> Result<(), ManifoldError> {
-        let splitters: Vec<_> = line
-            .as_bytes()
-            .iter()
+        let splitters: BitSet = line
+            .bytes()
             .enumerate()
-            .filter_map(|(i, c)| (*c == b'^').then_some(i))
+            .filter_map(|(i, c)| (c == b'^').then_some(i))
             .collect();
 
-        // Debug output - fill in all beams that aren't interrupted by a splitter
-        let mut line_debug = line.to_string();
-        for beam in self.beams.iter() {
-            if splitters
-                .iter()
-                .find(|splitter| *splitter == beam)
-                .is_none()
-            {
-                unsafe {
-                    line_debug.as_bytes_mut()[*beam] = b'|';
-                }
-            }
-        }
-
-        for splitter in splitters.iter() {
-            if self.beams.contains(splitter) {
-                self.beams.remove(splitter);
-                if *splitter > 0 {
-                    self.beams.insert(splitter - 1);
-                    unsafe {
-                        // Debug output - fill in a beam that was split
-                        line_debug.as_bytes_mut()[splitter - 1] = b'|';
-                    }
-                }
-                if *splitter < line.len() - 1 {
-                    self.beams.insert(splitter + 1);
-                    unsafe {
-                        // Debug output - fill in a beam that was split
-                        line_debug.as_bytes_mut()[splitter + 1] = b'|';
-                    }
-                }
-                self.splits += 1;
-            }
-        }
+        // Beams which hit a splitter continue one column to either side of it, as long as that's
+        // still inside the manifold.
+        let split = &self.beams & &splitters;
+        self.beams.difference_with(&split);
+        self.beams |= &(&split << 1);
+        self.beams |= &(&split >> 1);
+        self.beams.truncate(line.len());
+        self.splits += split.len() as u16;
 
+        // Debug output - fill in all beams
+        let line_debug: String = line
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match c {
+                '.' if self.beams.contains(i) => '|',
+                c => c,
+            })
+            .collect();
         tracing::debug!("tick: {}", line_debug);
 
         Ok(())
     }
 
+    #[tracing::instrument(skip_all)]
     pub fn run<B: BufRead>(mut lines: Lines<B>) -> Result<TachyonManifold, ManifoldError> {
         let mut manifold = TachyonManifold::default();
 
@@ -88,3 +76,25 @@ impl TachyonManifold {
         Ok(manifold)
     }
 }
+
+util::aoc_tests! {
+    example: "\
+.......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............
+",
+    part_1: "21",
+}
@@ -1,12 +1,11 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 
-use day7::*;
+use crate::*;
 
-fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
-    let manifold = TachyonManifold::run(reader.lines())?;
+pub fn solve(input: &[u8]) -> anyhow::Result<String> {
+    let manifold = TachyonManifold::run(input.lines())?;
     tracing::info!("Tachyon was split {} times.", manifold.splits);
     Ok(manifold.splits.to_string())
 }
 
-util::main!();
+util::solution!();
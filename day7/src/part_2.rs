@@ -1,14 +1,11 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 
-use day7::*;
-
-fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
-    for line in reader.lines() {
+pub fn solve(input: &[u8]) -> anyhow::Result<String> {
+    for line in input.lines() {
         tracing::trace!("{line:?}");
     }
 
     panic!("not implemented");
 }
 
-util::main!();
+util::solution!();
@@ -19,13 +19,17 @@ edition = "2024"
 name = "$day"
 path = "src/lib.rs"
 
+[[bin]]
+name = "$day"
+path = "src/main.rs"
+
 [[bin]]
 name = "$day-1"
-path = "src/part_1.rs"
+path = "src/bin/part_1.rs"
 
 [[bin]]
 name = "$day-2"
-path = "src/part_2.rs"
+path = "src/bin/part_2.rs"
 
 [dependencies]
 util = { workspace = true }
@@ -33,30 +37,44 @@ util = { workspace = true }
 anyhow = { workspace = true }
 thiserror = { workspace = true }
 tracing = { workspace = true }
-tracing-subscriber = { workspace = true }
 EOF
 
 # placeholder test files (need to fill in manually)
 touch $day/$day.input
 touch $day/$day.test.input
 
-# create starter `lib.rs`, `part_1.rs`, and `part_2.rs`
-touch $day/src/lib.rs
+# create starter `lib.rs`, `part_1.rs` and `part_2.rs`, and binaries which run them
+cat << EOF > $day/src/lib.rs
+pub mod part_1;
+pub mod part_2;
+
+// fill in the example input and its answers from the problem description
+util::aoc_tests! {
+    example: "",
+    part_1: "",
+    part_2: "",
+}
+EOF
+cat << EOF > $day/src/main.rs
+util::main!($day::part_1, $day::part_2);
+EOF
+mkdir -p $day/src/bin
+echo "util::main!($day::part_1);" > $day/src/bin/part_1.rs
+echo "util::main!($day::part_2);" > $day/src/bin/part_2.rs
 cat << EOF > $day/src/part_1.rs
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 
-use $day::*;
+use crate::*;
 
-fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
-    for line in reader.lines() {
+pub fn solve(input: &[u8]) -> anyhow::Result<String> {
+    for line in input.lines() {
         tracing::trace!("{line:?}");
     }
 
     panic!("not implemented");
 }
 
-util::main!();
+util::solution!();
 EOF
 cp $day/src/part_1.rs $day/src/part_2.rs
 
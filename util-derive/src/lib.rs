@@ -0,0 +1,370 @@
+//! Derive macros for `util`. Use them through their re-exports in `util` (e.g.
+//! `util::scan::AocParse`) rather than depending on this crate directly.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Attribute, Data, DeriveInput, Fields, Ident, LitStr, Member, Type, parse_macro_input};
+
+/// Implement `FromStr` and `util::scan::Scan` for a struct or enum from a pattern given in an
+/// `#[aoc(pattern = "...")]` attribute. See `util::scan::AocParse` for details.
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let (from_str, token_len) = match &input.data {
+        Data::Struct(data) => {
+            let pattern = pattern_attr(&input.attrs)?.ok_or_else(|| {
+                syn::Error::new(
+                    Span::call_site(),
+                    "missing `#[aoc(pattern = \"...\")]` attribute",
+                )
+            })?;
+            let record = Record::new(quote!(#name), name.to_string(), &data.fields, &pattern)?;
+            (record.parse(), None)
+        }
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "`AocParse` cannot be derived for enums without variants",
+                ));
+            }
+            let mut variants = vec![];
+            for variant in &data.variants {
+                let variant_name = &variant.ident;
+                let pattern = match pattern_attr(&variant.attrs)? {
+                    Some(pattern) => pattern,
+                    None if matches!(variant.fields, Fields::Unit) => {
+                        LitStr::new(&variant_name.to_string(), variant_name.span())
+                    }
+                    None => {
+                        return Err(syn::Error::new_spanned(
+                            variant_name,
+                            "missing `#[aoc(pattern = \"...\")]` attribute",
+                        ));
+                    }
+                };
+                variants.push(Record::new(
+                    quote!(#name::#variant_name),
+                    format!("{name}::{variant_name}"),
+                    &variant.fields,
+                    &pattern,
+                )?);
+            }
+            // Try longer prefixes first so that e.g. `LL` isn't parsed as `L` followed by `L`.
+            // Variants without a prefix match anything, so they come last.
+            variants.sort_by_key(|variant| std::cmp::Reverse(variant.prefix.len()));
+            enum_parsers(&variants)
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`AocParse` cannot be derived for unions",
+            ));
+        }
+    };
+
+    let scan_impl = match token_len {
+        Some(token_len) => quote! {
+            fn token_len(s: &str) -> usize {
+                #token_len
+            }
+        },
+        None => quote!(),
+    };
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #name #type_generics #where_clause {
+            type Err = ::util::scan::ScanError;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                #from_str
+            }
+        }
+
+        impl #impl_generics ::util::scan::Scan for #name #type_generics #where_clause {
+            #scan_impl
+        }
+    })
+}
+
+/// Build the body of `from_str` for an enum, plus the body of `token_len` if every variant is a
+/// unit variant.
+fn enum_parsers(variants: &[Record]) -> (TokenStream, Option<TokenStream>) {
+    // Several variants can share a prefix (or have none), so if one fails to parse, the next is
+    // tried. The error is from the first variant whose prefix matched, which has the longest one.
+    let branches = variants.iter().map(|variant| {
+        let prefix = &variant.prefix;
+        let parse = variant.parse();
+        quote! {
+            if s.starts_with(#prefix) {
+                let parsed = (|| -> ::std::result::Result<Self, ::util::scan::ScanError> {
+                    #parse
+                })();
+                match parsed {
+                    Ok(parsed) => return Ok(parsed),
+                    Err(error) => {
+                        first_error.get_or_insert(error);
+                    }
+                }
+            }
+        }
+    });
+    let expected = variants
+        .iter()
+        .map(|variant| format!("{:?}", variant.prefix))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let expected = format!("expected one of {expected}");
+    let from_str = quote! {
+        let mut first_error = None;
+        #(#branches)*
+        Err(first_error.unwrap_or_else(|| ::util::scan::ScanError::new(s, 1, #expected)))
+    };
+
+    // A unit variant is exactly its literal, so a field of this type can end right after it. If no
+    // variant matches, take the rest of the line so the error shows what was there instead.
+    let token_len = variants
+        .iter()
+        .all(|variant| variant.fields.is_empty())
+        .then(|| {
+            let prefixes = variants.iter().map(|variant| &variant.prefix);
+            quote! {
+                [#(#prefixes),*]
+                    .into_iter()
+                    .find(|prefix| s.starts_with(prefix))
+                    .map_or(s.len(), str::len)
+            }
+        });
+    (from_str, token_len)
+}
+
+/// Read the pattern from an `#[aoc(pattern = "...")]` attribute, if there is one.
+fn pattern_attr(attrs: &[Attribute]) -> syn::Result<Option<LitStr>> {
+    let mut pattern = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("aoc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("pattern") {
+                pattern = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `aoc` attribute; expected `pattern`"))
+            }
+        })?;
+    }
+    Ok(pattern)
+}
+
+/// A piece of an `#[aoc(pattern)]`.
+enum Segment {
+    Literal(String),
+
+    /// A `{}` field, or a `{name}` field naming a struct field or tuple index.
+    Field(String),
+}
+
+/// Split a pattern into literals and fields. `{{` and `}}` are literal braces.
+fn parse_pattern(pattern: &LitStr) -> syn::Result<Vec<Segment>> {
+    let mut segments = vec![];
+    let mut literal = String::new();
+    let value = pattern.value();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                literal.push(c);
+            }
+            ('{', _) => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => {
+                            return Err(syn::Error::new_spanned(
+                                pattern,
+                                "unclosed `{` in pattern",
+                            ));
+                        }
+                    }
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(name.trim().into()));
+            }
+            _ => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+/// A struct or enum variant and the pattern it's parsed from.
+struct Record {
+    /// How to construct the record, e.g. `Turn` or `Turn::L`.
+    path: TokenStream,
+
+    /// The fields of the record, in declaration order.
+    fields: Vec<RecordField>,
+
+    /// Whether the fields are named (`{ a, b }`) rather than positional (`(a, b)`).
+    named: bool,
+
+    /// The pattern with every field rewritten as `{}`, as `util::scan::Scanner` expects.
+    scanner_pattern: String,
+
+    /// Indexes into `fields`, in the order they appear in the pattern.
+    field_order: Vec<usize>,
+
+    /// The literal text the pattern starts with, which identifies an enum variant.
+    prefix: String,
+}
+
+struct RecordField {
+    member: Member,
+    ty: Type,
+
+    /// The name used in parse errors, e.g. `Turn::L.0`.
+    label: String,
+}
+
+impl Record {
+    fn new(
+        path: TokenStream,
+        record_name: String,
+        fields: &Fields,
+        pattern: &LitStr,
+    ) -> syn::Result<Record> {
+        let fields: Vec<RecordField> = fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let member = match &field.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(i.into()),
+                };
+                let label = match &field.ident {
+                    Some(ident) => format!("{record_name}.{ident}"),
+                    None => format!("{record_name}.{i}"),
+                };
+                RecordField {
+                    member,
+                    ty: field.ty.clone(),
+                    label,
+                }
+            })
+            .collect();
+
+        let segments = parse_pattern(pattern)?;
+        let prefix = match segments.first() {
+            Some(Segment::Literal(literal)) => literal.clone(),
+            _ => String::new(),
+        };
+
+        let mut scanner_pattern = String::new();
+        let mut field_order = vec![];
+        let mut next_positional = 0;
+        for segment in &segments {
+            match segment {
+                Segment::Literal(literal) => {
+                    scanner_pattern.push_str(&literal.replace('{', "{{").replace('}', "}}"));
+                }
+                Segment::Field(name) => {
+                    let index = if name.is_empty() {
+                        next_positional += 1;
+                        next_positional - 1
+                    } else {
+                        fields
+                            .iter()
+                            .position(|field| match &field.member {
+                                Member::Named(ident) => ident == name,
+                                Member::Unnamed(index) => index.index.to_string() == *name,
+                            })
+                            .ok_or_else(|| {
+                                syn::Error::new_spanned(
+                                    pattern,
+                                    format!("pattern refers to unknown field `{name}`"),
+                                )
+                            })?
+                    };
+                    if index >= fields.len() {
+                        return Err(syn::Error::new_spanned(
+                            pattern,
+                            format!("pattern has more fields than `{record_name}`"),
+                        ));
+                    }
+                    if field_order.contains(&index) {
+                        return Err(syn::Error::new_spanned(
+                            pattern,
+                            format!("pattern uses field `{}` twice", fields[index].label),
+                        ));
+                    }
+                    field_order.push(index);
+                    scanner_pattern.push_str("{}");
+                }
+            }
+        }
+        if let Some(missing) = (0..fields.len()).find(|index| !field_order.contains(index)) {
+            return Err(syn::Error::new_spanned(
+                pattern,
+                format!("pattern doesn't use field `{}`", fields[missing].label),
+            ));
+        }
+
+        Ok(Record {
+            path,
+            named: matches!(
+                fields.first(),
+                Some(RecordField {
+                    member: Member::Named(_),
+                    ..
+                })
+            ),
+            fields,
+            scanner_pattern,
+            field_order,
+            prefix,
+        })
+    }
+
+    /// An expression which parses `s` into this record, returning a `Result`.
+    fn parse(&self) -> TokenStream {
+        let scanner_pattern = &self.scanner_pattern;
+        let locals: Vec<Ident> = (0..self.fields.len())
+            .map(|i| format_ident!("field_{i}"))
+            .collect();
+        let parse_fields = self.field_order.iter().map(|&index| {
+            let RecordField { ty, label, .. } = &self.fields[index];
+            let local = &locals[index];
+            quote! {
+                let #local = scanner.named_field::<#ty>(#label)?;
+            }
+        });
+        let path: &TokenStream = &self.path;
+        let construct = if self.fields.is_empty() {
+            quote!(#path)
+        } else if self.named {
+            let members = self.fields.iter().map(|field| &field.member);
+            quote!(#path { #(#members: #locals),* })
+        } else {
+            quote!(#path(#(#locals),*))
+        };
+        quote! {
+            let mut scanner = ::util::scan::Scanner::new(s, #scanner_pattern);
+            #(#parse_fields)*
+            scanner.finish()?;
+            Ok(#construct)
+        }
+    }
+}
@@ -1,11 +1,12 @@
 use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
 use reqwest::{Url, blocking::Client, cookie::Jar, header};
 
-use crate::Problem;
+use crate::{Problem, cache::fingerprint};
 
 const AOC_BASE_URL: &str = "https://adventofcode.com";
 
@@ -46,9 +47,6 @@ pub enum AocError {
     #[error("failed to parse url")]
     UrlError,
 
-    #[error("unrecognized day: {0}")]
-    DayError(String),
-
     #[error("rate limit tripped")]
     RateLimited,
 
@@ -58,12 +56,6 @@ pub enum AocError {
     #[error("cannot parse AoC response")]
     UnrecognizedResponse,
 
-    #[error("unrecognized day: {0}")]
-    UnrecognizedDay(String),
-
-    #[error("unrecognized problem: {0}")]
-    UnrecognizedProblem(String),
-
     #[error("must set `$AOC_SESSION_COOKIE` env var")]
     MissingSession(#[from] std::env::VarError),
 
@@ -74,9 +66,54 @@ pub enum AocError {
     ClientError(#[from] reqwest::Error),
 }
 
+/// The request [`Aoc::submit`] sends to AoC for a solution.
+///
+/// ```
+/// # use util::aoc::Submission;
+/// let submission = Submission::new(&"day3-2".parse().unwrap(), "1182");
+/// assert_eq!(submission.url, "https://adventofcode.com/2025/day/3/answer");
+/// assert_eq!(submission.form["level"], "2");
+/// assert_eq!(submission.form["answer"], "1182");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    /// Where the form is POSTed.
+    pub url: String,
+
+    /// The form fields.
+    pub form: BTreeMap<String, String>,
+}
+
+impl Submission {
+    pub fn new(problem: &Problem, solution: &str) -> Submission {
+        let Problem { year, day, part } = problem;
+        Submission {
+            url: format!("{AOC_BASE_URL}/{year}/day/{day}/answer"),
+            form: BTreeMap::from_iter([
+                ("level".to_string(), part.to_string()),
+                ("answer".to_string(), solution.to_string()),
+            ]),
+        }
+    }
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "POST {}", self.url)?;
+        for (name, value) in self.form.iter() {
+            write!(f, "\n  {name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
 /// Advent of Code client.
+#[derive(Clone)]
 pub struct Aoc {
     client: Client,
+
+    /// A short fingerprint of the session cookie; see [`Aoc::session_fingerprint`].
+    session_fingerprint: String,
 }
 
 impl Aoc {
@@ -97,15 +134,34 @@ impl Aoc {
         let client = Client::builder()
             .cookie_provider(cookie_jar.into())
             .build()?;
-        Ok(Aoc { client })
+
+        // Keep a short, non-secret fingerprint of the session cookie around so cached solutions can
+        // record which login they were submitted from.
+        let session_fingerprint = fingerprint(session_cookie.as_bytes())
+            .trim_start_matches("sha256:")
+            .chars()
+            .take(12)
+            .collect();
+        Ok(Aoc {
+            client,
+            session_fingerprint,
+        })
+    }
+
+    /// Return a short fingerprint of the session cookie, which doesn't reveal the cookie itself.
+    ///
+    /// This identifies a login session, not an account: logging in again gives a new cookie, and
+    /// so a new fingerprint, for the same account.
+    pub fn session_fingerprint(&self) -> &str {
+        &self.session_fingerprint
     }
 
     /// View a day's input file.
     ///
     /// `day` is expected to be `day1`, `day2`, or similar, as it would be from `$CARGO_PKG_NAME`.
     pub fn view_input(&self, problem: &Problem) -> Result<String, AocError> {
-        let day = &problem.day;
-        let url = format!("{AOC_BASE_URL}/2025/day/{day}/input");
+        let Problem { year, day, .. } = problem;
+        let url = format!("{AOC_BASE_URL}/{year}/day/{day}/input");
         tracing::info!("Fetching {problem} input from {url}");
         Ok(self.client.get(url).send()?.text()?)
     }
@@ -130,19 +186,13 @@ impl Aoc {
     /// `problem` is expected to be `day1-1`, `day2-2`, or similar, as it would be from
     /// `$CARGO_BIN_NAME`.
     pub fn submit(&self, problem: &Problem, solution: &str) -> Result<AocResult, AocError> {
-        let day = &problem.day;
-        let url = format!("{AOC_BASE_URL}/2025/day/{day}/answer");
-        tracing::info!(?solution, "Posting to {url}");
-
-        let formdata = BTreeMap::from_iter([
-            ("level".to_string(), problem.part.as_str()),
-            ("answer".to_string(), solution),
-        ]);
-        tracing::info!("Form {:?}", formdata);
+        let submission = Submission::new(problem, solution);
+        tracing::info!(?solution, "Posting to {}", submission.url);
+        tracing::info!("Form {:?}", submission.form);
         let response = self
             .client
-            .post(url)
-            .form(&formdata)
+            .post(&submission.url)
+            .form(&submission.form)
             .header(header::USER_AGENT, USER_AGENT)
             .send()?;
 
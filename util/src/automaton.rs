@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::grid::{Adjacency, Grid, Point};
+
+/// How an [`Automaton`] decides which cells to apply its rule to each step. Either way, every
+/// cell's next state is computed from the grid as it was at the start of the step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    /// Apply the rule to every cell.
+    Synchronous,
+
+    /// Only apply the rule to cells which changed in the last step, or which have a neighbor that
+    /// did. Much faster when changes are sparse, and gives the same result as
+    /// [`Update::Synchronous`] as long as the rule only looks at a cell and its neighbors.
+    Worklist,
+}
+
+/// What happened in one step of an [`Automaton`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    /// How many steps have been run, including this one.
+    pub generation: usize,
+
+    /// How many cells changed in this step.
+    pub changed: usize,
+}
+
+/// A grid state which repeats; see [`Automaton::run_until_cycle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first generation whose grid repeats.
+    pub start: usize,
+
+    /// How many generations pass before it repeats. A grid which stopped changing has a period of
+    /// 1.
+    pub period: usize,
+}
+
+/// A callback run after each step of an [`Automaton`]; see [`Automaton::on_step`].
+type OnStep<'g, T> = Box<dyn FnMut(&Grid<T>, Step) + 'g>;
+
+/// Steps a grid forward with a rule that computes each cell's next state from its current state
+/// and its neighbors' states.
+///
+/// ```
+/// # use util::automaton::{Automaton, Update};
+/// # use util::grid::Grid;
+/// // Conway's Game of Life. A blinker flips between horizontal and vertical.
+/// let mut grid = Grid::parse(".....\n..#..\n..#..\n..#..\n.....\n", |c| Some(c == '#')).unwrap();
+/// let mut life = Automaton::new(&mut grid, |alive: &bool, neighbors: &[&bool]| {
+///     let living = neighbors.iter().filter(|alive| ***alive).count();
+///     living == 3 || (*alive && living == 2)
+/// })
+/// .with_update(Update::Worklist);
+///
+/// assert_eq!(life.step(), 4);
+/// assert_eq!(life.grid().find_all(&true).count(), 3);
+///
+/// let cycle = life.run_until_cycle();
+/// assert_eq!(cycle.period, 2);
+/// ```
+pub struct Automaton<'g, T, R> {
+    grid: &'g mut Grid<T>,
+    rule: R,
+
+    /// Which cells around each cell are passed to `rule`. Defaults to all 8.
+    neighborhood: Vec<Adjacency>,
+
+    update: Update,
+    generation: usize,
+
+    /// Cells to apply the rule to in the next [`Update::Worklist`] step.
+    worklist: Vec<Point>,
+
+    /// Whether each cell is already in `worklist`.
+    queued: Grid<bool>,
+
+    on_step: Option<OnStep<'g, T>>,
+}
+
+impl<'g, T, R> Automaton<'g, T, R>
+where
+    T: PartialEq,
+    R: FnMut(&T, &[&T]) -> T,
+{
+    /// Prepare to step `grid` forward with `rule`, which is passed each cell and its neighbors
+    /// (in the order of the neighborhood, skipping any outside the grid) and returns the cell's
+    /// next state.
+    pub fn new(grid: &'g mut Grid<T>, rule: R) -> Automaton<'g, T, R> {
+        Automaton {
+            worklist: grid.points().collect(),
+            queued: grid.map(|_| true),
+            grid,
+            rule,
+            neighborhood: Adjacency::ALL.to_vec(),
+            update: Update::Synchronous,
+            generation: 0,
+            on_step: None,
+        }
+    }
+
+    /// Pass the cells in each of `neighborhood`'s directions to the rule, e.g.
+    /// [`Adjacency::ORTHOGONAL`].
+    pub fn with_neighborhood(mut self, neighborhood: &[Adjacency]) -> Self {
+        self.neighborhood = neighborhood.to_vec();
+        self
+    }
+
+    pub fn with_update(mut self, update: Update) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// Call `on_step` with the grid after every step, e.g. to print it.
+    pub fn on_step(mut self, on_step: impl FnMut(&Grid<T>, Step) + 'g) -> Self {
+        self.on_step = Some(Box::new(on_step));
+        self
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        self.grid
+    }
+
+    /// How many steps have been run.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Apply the rule once. Returns how many cells changed.
+    pub fn step(&mut self) -> usize {
+        let points: Vec<Point> = match self.update {
+            Update::Synchronous => self.grid.points().collect(),
+            Update::Worklist => {
+                for point in self.worklist.iter() {
+                    self.queued[*point] = false;
+                }
+                std::mem::take(&mut self.worklist)
+            }
+        };
+
+        let grid = &*self.grid;
+        let mut changes = vec![];
+        let mut neighbors = Vec::with_capacity(self.neighborhood.len());
+        for point in points {
+            neighbors.clear();
+            neighbors.extend(grid.neighbors(point, &self.neighborhood).map(|p| &grid[p]));
+            let next = (self.rule)(&grid[point], &neighbors);
+            if next != grid[point] {
+                changes.push((point, next));
+            }
+        }
+
+        let changed = changes.len();
+        for (point, next) in changes {
+            self.grid[point] = next;
+            if self.update == Update::Worklist {
+                // The cells whose neighborhoods include `point` are the ones in the opposite
+                // directions.
+                let dependents = self
+                    .neighborhood
+                    .iter()
+                    .filter_map(|adj| self.grid.adjacent(point, adj.opposite()));
+                for dependent in std::iter::once(point).chain(dependents) {
+                    if !self.queued[dependent] {
+                        self.queued[dependent] = true;
+                        self.worklist.push(dependent);
+                    }
+                }
+            }
+        }
+
+        self.generation += 1;
+        let step = Step {
+            generation: self.generation,
+            changed,
+        };
+        if let Some(on_step) = self.on_step.as_mut() {
+            on_step(self.grid, step);
+        }
+        changed
+    }
+
+    /// Step until the grid stops changing. Returns how many steps changed something.
+    ///
+    /// This never returns if the grid cycles through several states; use
+    /// [`Automaton::run_until_cycle`] for rules which might.
+    pub fn run_until_stable(&mut self) -> usize {
+        let start = self.generation;
+        while self.step() > 0 {}
+        self.generation - start - 1
+    }
+
+    /// Step until the grid returns to a state it has been in before, which includes a grid that
+    /// stops changing. Every state is kept to compare against, so this needs memory for as many
+    /// grids as there are generations before the cycle.
+    pub fn run_until_cycle(&mut self) -> Cycle
+    where
+        T: Clone + Eq + Hash,
+    {
+        let mut seen = HashMap::from([(self.grid.clone(), self.generation)]);
+        loop {
+            if self.step() == 0 {
+                return Cycle {
+                    start: self.generation - 1,
+                    period: 1,
+                };
+            }
+            if let Some(start) = seen.get(&*self.grid) {
+                return Cycle {
+                    start: *start,
+                    period: self.generation - start,
+                };
+            }
+            seen.insert(self.grid.clone(), self.generation);
+        }
+    }
+}
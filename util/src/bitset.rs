@@ -0,0 +1,298 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Shl, ShlAssign, Shr, ShrAssign, Sub};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A set of small `usize`s stored one bit each, which grows as needed.
+///
+/// Set operations and shifts work a whole `u64` at a time, which makes it a good fit for
+/// simulations over a row of cells. For example, beams moving one column left and right:
+///
+/// ```
+/// # use util::bitset::BitSet;
+/// let beams: BitSet = [3, 7].into_iter().collect();
+/// let splitters: BitSet = [3].into_iter().collect();
+///
+/// let split = &beams & &splitters;
+/// let beams = &(&beams - &split) | &(&(&split << 1) | &(&split >> 1));
+/// assert_eq!(beams.iter().collect::<Vec<_>>(), [2, 4, 7]);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    /// Bit `i` of the set is bit `i % 64` of `words[i / 64]`. The last word is never zero, so
+    /// equal sets have equal `words`.
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> BitSet {
+        BitSet::default()
+    }
+
+    /// Drop zero words from the end to maintain the invariant on `words`.
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+
+    /// Add `value` to the set. Returns whether it was newly added.
+    ///
+    /// ```
+    /// # use util::bitset::BitSet;
+    /// let mut set = BitSet::new();
+    /// assert!(set.insert(100));
+    /// assert!(!set.insert(100));
+    /// assert!(set.contains(100));
+    /// assert!(!set.contains(99));
+    /// ```
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = (value / WORD_BITS, value % WORD_BITS);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let inserted = self.words[word] & (1 << bit) == 0;
+        self.words[word] |= 1 << bit;
+        inserted
+    }
+
+    /// Remove `value` from the set. Returns whether it was present.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (word, bit) = (value / WORD_BITS, value % WORD_BITS);
+        let Some(bits) = self.words.get_mut(word) else {
+            return false;
+        };
+        let removed = *bits & (1 << bit) != 0;
+        *bits &= !(1 << bit);
+        self.trim();
+        removed
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.words
+            .get(value / WORD_BITS)
+            .is_some_and(|bits| bits & (1 << (value % WORD_BITS)) != 0)
+    }
+
+    /// How many values are in the set.
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|bits| bits.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Remove every value.
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    /// Remove every value that is `len` or greater.
+    ///
+    /// ```
+    /// # use util::bitset::BitSet;
+    /// let mut set: BitSet = [1, 64, 65, 200].into_iter().collect();
+    /// set.truncate(65);
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), [1, 64]);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        let (word, bit) = (len / WORD_BITS, len % WORD_BITS);
+        if word < self.words.len() {
+            self.words.truncate(word + 1);
+            self.words[word] &= (1 << bit) - 1;
+            self.trim();
+        }
+    }
+
+    /// Iterate over the values in the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(word, &bits)| {
+            let mut bits = bits;
+            std::iter::from_fn(move || {
+                (bits != 0).then(|| {
+                    let bit = bits.trailing_zeros() as usize;
+                    bits &= bits - 1;
+                    word * WORD_BITS + bit
+                })
+            })
+        })
+    }
+
+    /// Add every value in `other` to this set.
+    pub fn union_with(&mut self, other: &BitSet) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (bits, other_bits) in self.words.iter_mut().zip(&other.words) {
+            *bits |= other_bits;
+        }
+    }
+
+    /// Remove every value which isn't in `other` from this set.
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        self.words.truncate(other.words.len());
+        for (bits, other_bits) in self.words.iter_mut().zip(&other.words) {
+            *bits &= other_bits;
+        }
+        self.trim();
+    }
+
+    /// Remove every value in `other` from this set.
+    pub fn difference_with(&mut self, other: &BitSet) {
+        for (bits, other_bits) in self.words.iter_mut().zip(&other.words) {
+            *bits &= !other_bits;
+        }
+        self.trim();
+    }
+
+    /// Add `n` to every value in the set.
+    ///
+    /// ```
+    /// # use util::bitset::BitSet;
+    /// let mut set: BitSet = [0, 63].into_iter().collect();
+    /// set.shift_left(1);
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), [1, 64]);
+    /// ```
+    pub fn shift_left(&mut self, n: usize) {
+        if self.words.is_empty() {
+            return;
+        }
+        let (words, bits) = (n / WORD_BITS, n % WORD_BITS);
+        let mut shifted = vec![0; self.words.len() + words + 1];
+        for (i, &word) in self.words.iter().enumerate() {
+            shifted[i + words] |= word << bits;
+            if bits > 0 {
+                shifted[i + words + 1] |= word >> (WORD_BITS - bits);
+            }
+        }
+        self.words = shifted;
+        self.trim();
+    }
+
+    /// Subtract `n` from every value in the set. Values less than `n` are removed.
+    ///
+    /// ```
+    /// # use util::bitset::BitSet;
+    /// let mut set: BitSet = [0, 1, 64].into_iter().collect();
+    /// set.shift_right(1);
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), [0, 63]);
+    /// ```
+    pub fn shift_right(&mut self, n: usize) {
+        let (words, bits) = (n / WORD_BITS, n % WORD_BITS);
+        if words >= self.words.len() {
+            self.words.clear();
+            return;
+        }
+        let shifted = (words..self.words.len())
+            .map(|i| {
+                let high = match (bits, self.words.get(i + 1)) {
+                    (0, _) | (_, None) => 0,
+                    (_, Some(next)) => next << (WORD_BITS - bits),
+                };
+                (self.words[i] >> bits) | high
+            })
+            .collect();
+        self.words = shifted;
+        self.trim();
+    }
+}
+
+impl Debug for BitSet {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, values: I) {
+        for value in values {
+            self.insert(value);
+        }
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(values: I) -> BitSet {
+        let mut set = BitSet::new();
+        set.extend(values);
+        set
+    }
+}
+
+impl BitOrAssign<&BitSet> for BitSet {
+    fn bitor_assign(&mut self, other: &BitSet) {
+        self.union_with(other);
+    }
+}
+
+impl BitAndAssign<&BitSet> for BitSet {
+    fn bitand_assign(&mut self, other: &BitSet) {
+        self.intersect_with(other);
+    }
+}
+
+impl ShlAssign<usize> for BitSet {
+    fn shl_assign(&mut self, n: usize) {
+        self.shift_left(n);
+    }
+}
+
+impl ShrAssign<usize> for BitSet {
+    fn shr_assign(&mut self, n: usize) {
+        self.shift_right(n);
+    }
+}
+
+impl BitOr for &BitSet {
+    type Output = BitSet;
+
+    fn bitor(self, other: &BitSet) -> BitSet {
+        let mut set = self.clone();
+        set.union_with(other);
+        set
+    }
+}
+
+impl BitAnd for &BitSet {
+    type Output = BitSet;
+
+    fn bitand(self, other: &BitSet) -> BitSet {
+        let mut set = self.clone();
+        set.intersect_with(other);
+        set
+    }
+}
+
+impl Sub for &BitSet {
+    type Output = BitSet;
+
+    fn sub(self, other: &BitSet) -> BitSet {
+        let mut set = self.clone();
+        set.difference_with(other);
+        set
+    }
+}
+
+impl Shl<usize> for &BitSet {
+    type Output = BitSet;
+
+    fn shl(self, n: usize) -> BitSet {
+        let mut set = self.clone();
+        set.shift_left(n);
+        set
+    }
+}
+
+impl Shr<usize> for &BitSet {
+    type Output = BitSet;
+
+    fn shr(self, n: usize) -> BitSet {
+        let mut set = self.clone();
+        set.shift_right(n);
+        set
+    }
+}
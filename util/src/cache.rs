@@ -0,0 +1,108 @@
+use std::fs::File;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Return a fingerprint (`sha256:<hex digest>`) of an input file's contents.
+///
+/// ```
+/// # use util::cache::fingerprint;
+/// assert_eq!(
+///     fingerprint(b"abc"),
+///     "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+/// );
+/// ```
+pub fn fingerprint(bytes: &[u8]) -> String {
+    let digest = Sha256::digest(bytes);
+    let hex: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("sha256:{hex}")
+}
+
+/// A correct solution saved locally, along with details about where it came from.
+///
+/// Cached solutions are stored as JSON. Older solution files which only contain the answer text
+/// are still accepted by [`CachedSolution::load`]; every field but `answer` will be `None`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CachedSolution {
+    /// The correct answer.
+    pub answer: String,
+
+    /// [`fingerprint`] of the input file that produced `answer`.
+    #[serde(default)]
+    pub input_hash: Option<String>,
+
+    /// When `answer` was submitted to AoC, in seconds since the Unix epoch.
+    #[serde(default)]
+    pub submitted_at: Option<u64>,
+
+    /// Which AoC login session `answer` was submitted from; see
+    /// [`crate::aoc::Aoc::session_fingerprint`].
+    #[serde(default)]
+    pub session_fingerprint: Option<String>,
+}
+
+impl CachedSolution {
+    /// Create a [`CachedSolution`] for an answer that was just accepted by AoC.
+    pub fn new(answer: &str, input_hash: &str, session_fingerprint: &str) -> CachedSolution {
+        let submitted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .ok();
+        CachedSolution {
+            answer: answer.into(),
+            input_hash: Some(input_hash.into()),
+            submitted_at,
+            session_fingerprint: Some(session_fingerprint.into()),
+        }
+    }
+
+    /// Read a cached solution from `filepath`.
+    ///
+    /// Files which don't start with `{` are treated as legacy plain-text solution files. Files
+    /// which do but aren't valid JSON are corrupt, and fail with [`ErrorKind::InvalidData`].
+    pub fn load<P: AsRef<Path>>(filepath: P) -> std::io::Result<CachedSolution> {
+        let filepath = filepath.as_ref();
+        let contents = std::fs::read_to_string(filepath)?;
+        if !contents.trim_start().starts_with('{') {
+            return Ok(CachedSolution {
+                answer: contents.trim().into(),
+                input_hash: None,
+                submitted_at: None,
+                session_fingerprint: None,
+            });
+        }
+        serde_json::from_str(&contents).map_err(|e| {
+            std::io::Error::new(
+                ErrorKind::InvalidData,
+                format!("corrupt cached solution `{}`: {e}", filepath.display()),
+            )
+        })
+    }
+
+    /// Like [`CachedSolution::load`], but `None` if there is no file at `filepath`.
+    pub fn load_if_exists<P: AsRef<Path>>(filepath: P) -> std::io::Result<Option<CachedSolution>> {
+        match CachedSolution::load(filepath) {
+            Ok(cached_solution) => Ok(Some(cached_solution)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write this cached solution to `filepath` as JSON.
+    pub fn save<P: AsRef<Path>>(&self, filepath: P) -> std::io::Result<()> {
+        let mut file = File::create(filepath)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        writeln!(file)
+    }
+
+    /// Whether this solution was produced from an input with fingerprint `input_hash`. Legacy
+    /// solution files don't record an input fingerprint, so they are assumed to match.
+    pub fn matches_input(&self, input_hash: &str) -> bool {
+        self.input_hash
+            .as_ref()
+            .is_none_or(|cached_hash| cached_hash == input_hash)
+    }
+}
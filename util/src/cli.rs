@@ -1,9 +1,19 @@
-use clap::{Args, Parser, Subcommand};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use clap::{ArgAction, Args, Parser, Subcommand};
 
 use crate::{
     Solution,
     aoc::Aoc,
-    runner::{RunArgs, run},
+    cache::CachedSolution,
+    compare::{CompareArgs, compare},
+    history,
+    inspect::inspect,
+    logging::{self, TracingGuard, TracingOptions},
+    runner::{RunArgs, print_reports, run},
+    submit::{EXAMPLE_INPUT, SubmitGuards},
+    watch::watch,
 };
 
 pub use clap;
@@ -17,12 +27,94 @@ pub struct SolveArgs {
     #[arg(short, long)]
     pub input: Option<String>,
 
+    /// Replace CRLF line endings with LF and remove trailing blank lines from the input before
+    /// running the solution. A warning is logged if this changes the input.
+    #[arg(short, long)]
+    #[clap(default_value_t = false)]
+    pub normalize: bool,
+
     /// Whether to submit the solution to Advent of Code.
     ///
     /// Expects `$AOC_SESSION_COOKIE` env var to be set; see [`crate::aoc::Aoc`].
     #[arg(short, long)]
     #[clap(default_value_t = false)]
     pub submit: bool,
+
+    /// With `--submit`, show the form that would be POSTed to Advent of Code without sending it.
+    /// Doesn't require `$AOC_SESSION_COOKIE`.
+    #[arg(long, requires = "submit")]
+    #[clap(default_value_t = false)]
+    pub dry_run: bool,
+
+    /// With `--submit`, don't ask for confirmation before submitting.
+    #[arg(short, long, requires = "submit")]
+    #[clap(default_value_t = false)]
+    pub yes: bool,
+
+    /// With `--submit`, allow submitting answers which aren't integers.
+    #[arg(long, requires = "submit")]
+    #[clap(default_value_t = false)]
+    pub allow_non_numeric: bool,
+
+    /// With `--submit`, allow submitting an answer of zero.
+    #[arg(long, requires = "submit")]
+    #[clap(default_value_t = false)]
+    pub allow_zero: bool,
+
+    /// With `--submit`, the answer for the example input given in the problem, which won't be
+    /// submitted. When running both parts, give it once for each part, in order.
+    ///
+    /// Defaults to the cached solution for the example input (e.g. `day1-1.test.solution`).
+    #[arg(long, requires = "submit", value_name = "ANSWER")]
+    pub example_answer: Vec<String>,
+
+    /// Rebuild and rerun the solution whenever its source or input file changes.
+    #[arg(short, long, conflicts_with = "submit")]
+    #[clap(default_value_t = false)]
+    pub watch: bool,
+
+    /// Report peak heap usage, number of allocations and bytes allocated by the solution.
+    ///
+    /// Requires `util`'s `mem` feature; see [`crate::mem::CountingAllocator`].
+    #[arg(short, long)]
+    #[clap(default_value_t = false)]
+    pub mem: bool,
+
+    /// Run every registered implementation of the solution on the same input(s) and report
+    /// disagreements and relative speed, rather than running the solution normally.
+    #[arg(short, long, conflicts_with_all = ["submit", "watch"])]
+    #[clap(default_value_t = false)]
+    pub compare: bool,
+
+    /// With `--compare`, also compare implementations on this many randomly generated inputs.
+    /// Requires the solution to register an input generator.
+    #[arg(long, requires = "compare")]
+    #[clap(default_value_t = 0)]
+    pub random: usize,
+
+    /// With `--random`, the seed for the first random input. Defaults to the current time.
+    #[arg(long, requires = "random")]
+    pub seed: Option<u64>,
+}
+
+/// Command line arguments related to the [`Command::History`] command.
+#[derive(Args, Clone)]
+pub struct HistoryArgs {
+    /// Show history for a custom input file instead of the default one.
+    ///
+    /// For example, `--input test` will show runs which used `day1.test.input`.
+    #[arg(short, long)]
+    pub input: Option<String>,
+}
+
+/// Command line arguments related to the [`Command::InspectInput`] command.
+#[derive(Args, Clone)]
+pub struct InspectInputArgs {
+    /// Inspect a custom input file instead of the default one.
+    ///
+    /// For example, `--input test` will inspect `day1.test.input`.
+    #[arg(short, long)]
+    pub input: Option<String>,
 }
 
 /// Actions that an AoC solution binary can perform.
@@ -35,6 +127,13 @@ pub enum Command {
     ///
     /// Expects `$AOC_SESSION_COOKIE` env var to be set; see [`crate::aoc::Aoc`].
     DownloadInput,
+
+    /// Show how this solution's answer and runtime evolved across commits.
+    History(HistoryArgs),
+
+    /// Report facts about the input file (line lengths, characters, integer ranges) to help
+    /// choose how to parse it.
+    InspectInput(InspectInputArgs),
 }
 
 /// CLI for Advent of Code solutions.
@@ -44,6 +143,8 @@ pub enum Command {
 /// - running solutions locally
 /// - submitting solutions to AoC
 /// - saving correct solutions locally
+/// - showing the history of a solution's answers and runtimes
+/// - inspecting input files
 ///
 /// Provide an AoC session cookie via the `$AOC_SESSION_COOKIE` environment variable. The cookie
 /// must begin with `session=`.
@@ -58,6 +159,36 @@ pub struct SolutionCli {
     /// allows solution binaries to omit the `solve` command.
     #[clap(flatten)]
     solve_args: SolveArgs,
+
+    /// Log more detail. Repeat for even more (`-vv` logs everything).
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+
+    /// Log less detail. Repeat for even less (`-qqq` logs nothing).
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    quiet: u8,
+
+    /// Log filter like `day4=trace,info`. Overrides `-v`/`-q` and `$RUST_LOG`.
+    #[arg(long, global = true, value_name = "FILTER")]
+    log: Option<String>,
+
+    /// Write logs to a file instead of the terminal. Warnings and errors are still shown.
+    ///
+    /// Defaults to `dayX/logs/<timestamp>.log` if no path is given. A path must be given as
+    /// `--log-file=<PATH>`, so that `--log-file solve` doesn't take the command as its path.
+    #[arg(long, global = true, value_name = "PATH", require_equals = true)]
+    log_file: Option<Option<PathBuf>>,
+
+    /// Print a summary of time spent in each `tracing` span (e.g. `#[instrument]`ed functions)
+    /// at exit.
+    #[arg(long, global = true)]
+    #[clap(default_value_t = false)]
+    timings: bool,
+
+    /// Write every `tracing` span to this file as Chrome trace event JSON at exit. The file can be
+    /// loaded into trace viewers like Perfetto or `chrome://tracing`.
+    #[arg(long, global = true)]
+    trace_json: Option<PathBuf>,
 }
 
 impl SolutionCli {
@@ -69,21 +200,172 @@ impl SolutionCli {
             .unwrap_or(Command::Solve(self.solve_args.clone()))
     }
 
-    /// Run the CLI for an Advent of Code solution.
-    pub fn run(&self, solution: &Solution) -> anyhow::Result<String> {
+    /// Install a global `tracing` subscriber according to the CLI's options. `working_dir` is the
+    /// day's directory, where the default log file is created. Hold on to the returned
+    /// [`TracingGuard`] until the program exits.
+    pub fn init_tracing(&self, working_dir: &Path) -> anyhow::Result<TracingGuard> {
+        let log_file = self.log_file.as_ref().map(|log_file| {
+            log_file
+                .clone()
+                .unwrap_or_else(|| logging::default_log_file(working_dir))
+        });
+        logging::init(&TracingOptions {
+            verbosity: self.verbose as i8 - self.quiet as i8,
+            log: self.log.clone(),
+            log_file,
+            timings: self.timings,
+            trace_json: self.trace_json.clone(),
+        })
+    }
+
+    /// The arguments for the `solve` command which [`watch`] reruns, so that each rerun behaves
+    /// like running without `--watch`. These are every option that can be combined with `--watch`
+    /// besides `--input`, which `watch` passes itself. Paths are made absolute, since the rerun
+    /// happens in the day's directory.
+    pub fn watch_args(&self, solve_args: &SolveArgs) -> std::io::Result<Vec<OsString>> {
+        let mut args: Vec<OsString> = vec![];
+        if solve_args.normalize {
+            args.push("--normalize".into());
+        }
+        if solve_args.mem {
+            args.push("--mem".into());
+        }
+        for _ in 0..self.verbose {
+            args.push("--verbose".into());
+        }
+        for _ in 0..self.quiet {
+            args.push("--quiet".into());
+        }
+        if let Some(log) = &self.log {
+            args.push(format!("--log={log}").into());
+        }
+        match &self.log_file {
+            Some(Some(log_file)) => {
+                let mut arg = OsString::from("--log-file=");
+                arg.push(std::path::absolute(log_file)?);
+                args.push(arg);
+            }
+            Some(None) => args.push("--log-file".into()),
+            None => {}
+        }
+        if self.timings {
+            args.push("--timings".into());
+        }
+        if let Some(trace_json) = &self.trace_json {
+            args.push("--trace-json".into());
+            args.push(std::path::absolute(trace_json)?.into());
+        }
+        Ok(args)
+    }
+
+    /// Run the CLI for one or more Advent of Code solutions from the same day.
+    ///
+    /// When more than one solution is given (e.g. both parts of a day), [`Command::Solve`] runs
+    /// each of them on the same input and prints a summary table.
+    pub fn run(&self, solutions: &[Solution]) -> anyhow::Result<String> {
+        let Some(first_solution) = solutions.first() else {
+            return Ok("".into());
+        };
         match self.command() {
+            Command::Solve(solve_args) if solve_args.watch => {
+                watch(solutions, &solve_args.input, &self.watch_args(&solve_args)?)?;
+            }
+            Command::Solve(solve_args) if solve_args.compare => {
+                let compare_args = CompareArgs {
+                    input: solve_args.input.clone(),
+                    random: solve_args.random,
+                    seed: solve_args.seed,
+                };
+                for solution in solutions {
+                    compare(solution, &compare_args)?;
+                }
+            }
             Command::Solve(solve_args) => {
-                let aoc_client = solve_args.submit.then_some(Aoc::new()).transpose()?;
-                run(&RunArgs {
-                    problem: solution.problem.clone(),
-                    solve_fn: solution.solve_fn,
-                    input_filepath: solution.input_file(&solve_args.input),
-                    solution_filepath: solution.solution_file(&solve_args.input),
-                    aoc_client,
-                })?;
+                let aoc_client = (solve_args.submit && !solve_args.dry_run)
+                    .then(Aoc::new)
+                    .transpose()?;
+                // Every solution is from the same day, so they all read the same input.
+                let input_bytes = std::fs::read(first_solution.input_file(&solve_args.input))?;
+                let mut results = vec![];
+                for (i, solution) in solutions.iter().enumerate() {
+                    let example_answer = solve_args.example_answer.get(i).cloned().or_else(|| {
+                        CachedSolution::load(solution.solution_file(&Some(EXAMPLE_INPUT.into())))
+                            .ok()
+                            .map(|example| example.answer)
+                    });
+                    let args = RunArgs {
+                        problem: solution.problem,
+                        solve_fn: solution.solve_fn,
+                        input: solve_args.input.clone(),
+                        input_filepath: solution.input_file(&solve_args.input),
+                        solution_filepath: solution.solution_file(&solve_args.input),
+                        history_filepath: solution.history_file(),
+                        aoc_client: aoc_client.clone(),
+                        dry_run: solve_args.dry_run,
+                        submit_guards: SubmitGuards {
+                            allow_non_numeric: solve_args.allow_non_numeric,
+                            allow_zero: solve_args.allow_zero,
+                            example_answer,
+                            confirm: !solve_args.yes,
+                        },
+                        normalize: solve_args.normalize,
+                        mem: solve_args.mem,
+                    };
+                    // A failing part shouldn't stop the others from running.
+                    let result = run(&args, &input_bytes);
+                    if let Err(e) = &result
+                        && solutions.len() > 1
+                    {
+                        tracing::error!("{} failed: {e:#}", solution.problem);
+                    }
+                    results.push((solution.problem, result));
+                }
+                // Answers go to stdout on their own lines so they can be piped. Everything else
+                // goes to stderr.
+                for (_, result) in results.iter() {
+                    if let Ok(report) = result {
+                        println!("{}", report.solution);
+                    }
+                }
+                // Allocation statistics don't fit on the answer's line, so they're shown in the
+                // summary table even for a single part.
+                if results.len() > 1 || solve_args.mem {
+                    print_reports(&results)?;
+                }
+                let mut failed = vec![];
+                for (problem, result) in results {
+                    if let Err(e) = result {
+                        if solutions.len() == 1 {
+                            return Err(e);
+                        }
+                        failed.push(problem.to_string());
+                    }
+                }
+                if !failed.is_empty() {
+                    anyhow::bail!("{} failed", failed.join(", "));
+                }
             }
             Command::DownloadInput => {
-                Aoc::new()?.download_input(&solution.problem, solution.input_file(&None))?;
+                Aoc::new()?
+                    .download_input(&first_solution.problem, first_solution.input_file(&None))?;
+            }
+            Command::History(history_args) => {
+                let records = history::load(first_solution.history_file())?;
+                for solution in solutions {
+                    if solutions.len() > 1 {
+                        println!("{}:", solution.problem);
+                    }
+                    history::print_history(
+                        &records,
+                        &solution.problem.to_string(),
+                        &history_args.input,
+                    )?;
+                }
+            }
+            Command::InspectInput(inspect_args) => {
+                let input_filepath = first_solution.input_file(&inspect_args.input);
+                println!("{}:", input_filepath.display());
+                print!("{}", inspect(&input_filepath)?);
             }
         }
         Ok("".into())
@@ -0,0 +1,125 @@
+use std::ops::Range;
+
+/// Text laid out in aligned blocks of columns, e.g. numbers stacked on top of each other with
+/// spaces between each stack.
+///
+/// ```text
+/// 123 328  51
+///  45 64  387
+///   6 98  215
+/// ```
+///
+/// Blocks are separated by columns which are a space in every row. Rows shorter than the longest
+/// row are treated as if they were padded with spaces, so ragged rows are safe to slice.
+///
+/// ```
+/// # use util::columns::ColumnLayout;
+/// let layout = ColumnLayout::new(["123 328  51", " 45 64  387", "  6 98"]);
+/// assert_eq!(layout.len(), 3);
+///
+/// let blocks: Vec<_> = layout.blocks().collect();
+/// assert_eq!(blocks[0].span(), 0..3);
+/// assert_eq!(blocks[0].rows().collect::<Vec<_>>(), ["123", " 45", "  6"]);
+/// assert_eq!(blocks[2].rows().collect::<Vec<_>>(), [" 51", "387", "   "]);
+///
+/// // Each column read top to bottom, from the right.
+/// assert_eq!(blocks[1].columns().rev().collect::<Vec<_>>(), ["8  ", "248", "369"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnLayout {
+    /// Every row, padded with spaces to the same width.
+    rows: Vec<Vec<char>>,
+
+    /// The columns each block spans.
+    spans: Vec<Range<usize>>,
+}
+
+impl ColumnLayout {
+    pub fn new(rows: impl IntoIterator<Item = impl AsRef<str>>) -> ColumnLayout {
+        let mut rows: Vec<Vec<char>> = rows
+            .into_iter()
+            .map(|row| row.as_ref().chars().collect())
+            .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for row in rows.iter_mut() {
+            row.resize(width, ' ');
+        }
+
+        let mut spans = vec![];
+        let mut start = None;
+        for col in 0..=width {
+            let separator = col == width || rows.iter().all(|row| row[col] == ' ');
+            match (separator, start) {
+                (false, None) => start = Some(col),
+                (true, Some(block_start)) => {
+                    spans.push(block_start..col);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        ColumnLayout { rows, spans }
+    }
+
+    /// How many rows there are.
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The length of the longest row, which every row is padded to.
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+
+    /// How many blocks there are.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Iterate over the blocks from left to right.
+    pub fn blocks(&self) -> impl DoubleEndedIterator<Item = Block<'_>> + ExactSizeIterator {
+        self.spans.iter().map(|span| Block {
+            rows: &self.rows,
+            span: span.clone(),
+        })
+    }
+}
+
+/// One block of a [`ColumnLayout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block<'a> {
+    rows: &'a [Vec<char>],
+    span: Range<usize>,
+}
+
+impl<'a> Block<'a> {
+    /// The columns of the layout this block spans.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    pub fn width(&self) -> usize {
+        self.span.len()
+    }
+
+    /// Iterate over this block's slice of each row, from top to bottom. Every row is
+    /// [`Block::width`] characters long, with alignment preserved.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = String> + ExactSizeIterator + 'a {
+        let span = self.span();
+        self.rows
+            .iter()
+            .map(move |row| row[span.clone()].iter().collect())
+    }
+
+    /// Iterate over this block's columns from left to right, each read from top to bottom. Use
+    /// `.rev()` to read the columns from right to left.
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = String> + ExactSizeIterator + 'a {
+        let rows = self.rows;
+        self.span()
+            .map(move |col| rows.iter().map(|row| row[col]).collect())
+    }
+}
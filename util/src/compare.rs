@@ -0,0 +1,245 @@
+use std::io::Write;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{Solution, SolveFn};
+
+/// Type alias for input generators. A generator should return the text of a random (but valid)
+/// input file for its problem, drawing randomness from the provided [`Rng`].
+pub type GenerateFn = fn(&mut Rng) -> String;
+
+/// A small, deterministic pseudo-random number generator (SplitMix64) for input generators.
+///
+/// ```
+/// # use util::compare::Rng;
+/// let mut a = Rng::new(42);
+/// let mut b = Rng::new(42);
+/// assert_eq!(a.next_u64(), b.next_u64());
+///
+/// let roll = a.range(1, 6);
+/// assert!((1..=6).contains(&roll));
+/// ```
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create an [`Rng`] from `seed`. The same seed always produces the same sequence.
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    /// Return the next pseudo-random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Return a pseudo-random number between `min` and `max`, inclusive.
+    pub fn range(&mut self, min: u64, max: u64) -> u64 {
+        assert!(min <= max);
+        match (max - min).checked_add(1) {
+            Some(span) => min + self.next_u64() % span,
+            None => self.next_u64(),
+        }
+    }
+
+    /// Return `true` with probability `1 / n`.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.range(1, n) == 1
+    }
+}
+
+/// The outcome of running one implementation on one input.
+struct Outcome {
+    name: &'static str,
+    answer: Result<String, String>,
+    duration: Duration,
+}
+
+/// Every implementation registered for `solution`, starting with the main `solve()` function.
+fn implementations(solution: &Solution) -> Vec<(&'static str, SolveFn)> {
+    std::iter::once(("solve", solution.solve_fn))
+        .chain(solution.alternatives.iter().copied())
+        .collect()
+}
+
+/// Run every implementation of `solution` on `input`.
+fn run_all(solution: &Solution, input: &[u8]) -> Vec<Outcome> {
+    let mut outcomes = vec![];
+    for (name, solve_fn) in implementations(solution) {
+        let start = Instant::now();
+        let answer = solve_fn(input).map_err(|e| e.to_string());
+        let duration = start.elapsed();
+        tracing::debug!("{name} finished in {duration:.2?}: {answer:?}");
+        outcomes.push(Outcome {
+            name,
+            answer,
+            duration,
+        });
+    }
+    outcomes
+}
+
+/// Whether every implementation produced the same successful answer.
+fn agree(outcomes: &[Outcome]) -> bool {
+    outcomes
+        .iter()
+        .all(|outcome| outcome.answer.is_ok() && outcome.answer == outcomes[0].answer)
+}
+
+/// Format how long `duration` is relative to `reference`, e.g. `2.50x`. Durations are floored at
+/// 1ns so an implementation too fast for the clock doesn't divide by zero.
+fn relative(duration: Duration, reference: Duration) -> String {
+    let nanos = |duration: Duration| duration.as_nanos().max(1) as f64;
+    format!("{:.2}x", nanos(duration) / nanos(reference))
+}
+
+fn print_outcomes(outcomes: &[Outcome]) -> anyhow::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    writeln!(
+        stdout,
+        "{:<16} {:>12} {:>10} ANSWER",
+        "IMPLEMENTATION", "TIME", "RELATIVE"
+    )?;
+    let reference = &outcomes[0];
+    for outcome in outcomes {
+        let mismatch = outcome.answer != reference.answer;
+        writeln!(
+            stdout,
+            "{:<16} {:>12} {:>10} {}{}",
+            outcome.name,
+            format!("{:.2?}", outcome.duration),
+            relative(outcome.duration, reference.duration),
+            match &outcome.answer {
+                Ok(answer) => answer.clone(),
+                Err(e) => format!("error: {e}"),
+            },
+            if mismatch { "  <- MISMATCH" } else { "" },
+        )?;
+    }
+    Ok(())
+}
+
+/// Options for [`compare`].
+pub struct CompareArgs {
+    /// The custom input name (e.g. `test`), or `None` for the default input. Skipped if the input
+    /// file doesn't exist.
+    pub input: Option<String>,
+
+    /// How many random inputs to generate, if the solution has a generator.
+    pub random: usize,
+
+    /// Seed for the first random input. Each subsequent input uses the next seed.
+    pub seed: Option<u64>,
+}
+
+/// Run every implementation registered for `solution` (its main `solve()` function and each of
+/// its alternatives) on the same inputs, and report disagreements and relative speed.
+///
+/// Random inputs which cause a disagreement are saved as `dayX.compare-<seed>.input` so they can
+/// be rerun with `--input compare-<seed>`.
+pub fn compare(solution: &Solution, args: &CompareArgs) -> anyhow::Result<()> {
+    if solution.alternatives.is_empty() {
+        tracing::warn!(
+            "{} has no alternative implementations to compare against",
+            solution.problem
+        );
+    }
+
+    let mut disagreements = 0;
+
+    let input_filepath = solution.input_file(&args.input);
+    if input_filepath.exists() {
+        println!("{} on `{}`:", solution.problem, input_filepath.display());
+        let outcomes = run_all(solution, &std::fs::read(&input_filepath)?);
+        print_outcomes(&outcomes)?;
+        if !agree(&outcomes) {
+            disagreements += 1;
+        }
+    } else {
+        tracing::warn!("`{}` doesn't exist; skipping", input_filepath.display());
+    }
+
+    if args.random > 0 {
+        match solution.generator {
+            Some(generator) => {
+                disagreements += compare_random(solution, generator, args)?;
+            }
+            None => tracing::warn!(
+                "{} has no input generator; skipping random inputs",
+                solution.problem
+            ),
+        }
+    }
+
+    if disagreements > 0 {
+        anyhow::bail!("implementations disagreed on {disagreements} input(s)");
+    }
+    Ok(())
+}
+
+/// Run every implementation on `args.random` generated inputs. Returns the number of inputs the
+/// implementations disagreed on.
+fn compare_random(
+    solution: &Solution,
+    generator: GenerateFn,
+    args: &CompareArgs,
+) -> anyhow::Result<usize> {
+    let first_seed = args.seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+    let implementations = implementations(solution);
+    let mut total_durations = vec![Duration::ZERO; implementations.len()];
+    let mut disagreements = 0;
+    for seed in (first_seed..).take(args.random) {
+        let input = generator(&mut Rng::new(seed));
+        let outcomes = run_all(solution, input.as_bytes());
+        for (total, outcome) in total_durations.iter_mut().zip(outcomes.iter()) {
+            *total += outcome.duration;
+        }
+        if !agree(&outcomes) {
+            disagreements += 1;
+            let saved_filepath = solution.input_file(&Some(format!("compare-{seed}")));
+            std::fs::write(&saved_filepath, &input)?;
+            println!(
+                "{} disagreement on seed {seed} (saved to `{}`):",
+                solution.problem,
+                saved_filepath.display()
+            );
+            print_outcomes(&outcomes)?;
+        }
+    }
+
+    println!(
+        "{} on {} random inputs (seeds {first_seed}..):",
+        solution.problem, args.random
+    );
+    let mut stdout = std::io::stdout().lock();
+    writeln!(
+        stdout,
+        "{:<16} {:>12} {:>10}",
+        "IMPLEMENTATION", "TOTAL TIME", "RELATIVE"
+    )?;
+    for ((name, _), total) in implementations.iter().zip(total_durations.iter()) {
+        writeln!(
+            stdout,
+            "{:<16} {:>12} {:>10}",
+            name,
+            format!("{total:.2?}"),
+            relative(*total, total_durations[0]),
+        )?;
+    }
+    writeln!(
+        stdout,
+        "{disagreements} of {} random inputs had disagreements",
+        args.random
+    )?;
+
+    Ok(disagreements)
+}
@@ -0,0 +1,211 @@
+use crate::math::Integer;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum DigitError {
+    #[error("invalid base {radix} digit {c:?} at position {position}")]
+    InvalidDigit {
+        c: char,
+        position: usize,
+        radix: u32,
+    },
+
+    #[error("no digits to parse")]
+    Empty,
+
+    #[error("number is too large for its type")]
+    Overflow,
+}
+
+/// `radix` as a `T`. Panics unless `radix` is in `2..=36`, like [`char::to_digit`].
+fn radix_of<T: Integer>(radix: u32) -> T {
+    assert!(
+        (2..=36).contains(&radix),
+        "radix must be in 2..=36, not {radix}"
+    );
+    T::from_u8(radix as u8)
+}
+
+/// How many digits `n` has in base `radix`, ignoring its sign. 0 has one digit.
+///
+/// ```
+/// # use util::digits::count;
+/// assert_eq!(count(1234512345u64, 10), 10);
+/// assert_eq!(count(0u8, 10), 1);
+/// assert_eq!(count(255u8, 2), 8);
+/// assert_eq!(count(-4096i32, 16), 4);
+/// ```
+pub fn count<T: Integer>(mut n: T, radix: u32) -> u32 {
+    let radix = radix_of::<T>(radix);
+    let mut count = 1;
+    // Dividing first avoids negating `MIN`.
+    while n / radix != T::ZERO {
+        n = n / radix;
+        count += 1;
+    }
+    count
+}
+
+/// `radix^exp`. Expects it to fit in `T`.
+fn pow<T: Integer>(radix: T, exp: u32) -> T {
+    (0..exp).fold(T::ONE, |power, _| power * radix)
+}
+
+/// Iterator over the digits of a number; see [`digits`].
+#[derive(Debug, Clone)]
+pub struct Digits<T> {
+    /// The digits which haven't been yielded yet, as a number.
+    remaining: T,
+
+    /// How many digits haven't been yielded yet, including leading zeros.
+    len: u32,
+
+    radix: T,
+}
+
+impl<T: Integer> Iterator for Digits<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.len = self.len.checked_sub(1)?;
+        let place = pow(self.radix, self.len);
+        let digit = (self.remaining / place).wrapping_abs();
+        self.remaining = self.remaining % place;
+        Some(digit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len as usize, Some(self.len as usize))
+    }
+}
+
+impl<T: Integer> DoubleEndedIterator for Digits<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.len = self.len.checked_sub(1)?;
+        let digit = (self.remaining % self.radix).wrapping_abs();
+        self.remaining = self.remaining / self.radix;
+        Some(digit)
+    }
+}
+
+impl<T: Integer> ExactSizeIterator for Digits<T> {}
+
+/// Iterate over the digits of `n` in base `radix`, most significant first, ignoring its sign.
+/// Use `.rev()` to start from the least significant digit.
+///
+/// ```
+/// # use util::digits::digits;
+/// assert_eq!(digits(1005u32, 10).collect::<Vec<_>>(), vec![1, 0, 0, 5]);
+/// assert_eq!(digits(1005u32, 10).rev().collect::<Vec<_>>(), vec![5, 0, 0, 1]);
+/// assert_eq!(digits(0xbeefu16, 16).collect::<Vec<_>>(), vec![0xb, 0xe, 0xe, 0xf]);
+/// assert_eq!(digits(-42i8, 10).collect::<Vec<_>>(), vec![4, 2]);
+/// ```
+pub fn digits<T: Integer>(n: T, radix: u32) -> Digits<T> {
+    Digits {
+        remaining: n,
+        len: count(n, radix),
+        radix: radix_of(radix),
+    }
+}
+
+/// Split the digits of `n` into `k` chunks of equal length, most significant first, or return
+/// `None` if its digit count isn't a multiple of `k`. Chunks may have leading zeros, which are
+/// lost (e.g. `1001` splits into `10` and `1`).
+///
+/// ```
+/// # use util::digits::split;
+/// assert_eq!(split(123123123u64, 3, 10), Some(vec![123, 123, 123]));
+/// assert_eq!(split(1001u64, 2, 10), Some(vec![10, 1]));
+/// assert_eq!(split(12345u64, 2, 10), None);
+/// ```
+pub fn split<T: Integer>(n: T, k: u32, radix: u32) -> Option<Vec<T>> {
+    let len = count(n, radix);
+    if k == 0 || !len.is_multiple_of(k) {
+        return None;
+    }
+    if k == 1 {
+        // `radix^len` may not fit in `T`.
+        return Some(vec![n]);
+    }
+    let chunk = pow(radix_of::<T>(radix), len / k);
+    let mut remaining = n;
+    let mut chunks: Vec<T> = (0..k)
+        .map(|_| {
+            let chunk_value = remaining % chunk;
+            remaining = remaining / chunk;
+            chunk_value
+        })
+        .collect();
+    chunks.reverse();
+    Some(chunks)
+}
+
+/// Append the digits of `b` to the digits of `a` in base `radix`, or return `None` on overflow.
+///
+/// ```
+/// # use util::digits::concat;
+/// assert_eq!(concat(12u32, 345, 10), Some(12345));
+/// assert_eq!(concat(12u32, 0, 10), Some(120));
+/// assert_eq!(concat(0b101u8, 0b11, 2), Some(0b10111));
+/// assert_eq!(concat(255u8, 1, 10), None);
+/// ```
+pub fn concat<T: Integer>(a: T, b: T, radix: u32) -> Option<T> {
+    let shift =
+        (0..count(b, radix)).try_fold(T::ONE, |power, _| power.checked_mul(radix_of(radix)))?;
+    a.checked_mul(shift)?.checked_add(b)
+}
+
+/// Repeat the digits of `block` `times` times in base `radix`, or return `None` on overflow.
+///
+/// ```
+/// # use util::digits::repeat;
+/// assert_eq!(repeat(123u64, 3, 10), Some(123123123));
+/// assert_eq!(repeat(7u64, 1, 10), Some(7));
+/// assert_eq!(repeat(12u8, 2, 10), None);
+/// ```
+pub fn repeat<T: Integer>(block: T, times: u32, radix: u32) -> Option<T> {
+    (1..times).try_fold(block, |repeated, _| concat(repeated, block, radix))
+}
+
+/// Parse a single digit character in base `radix`. `position` is only used for errors.
+///
+/// ```
+/// # use util::digits::{DigitError, parse_digit};
+/// assert_eq!(parse_digit::<u64>('7', 10, 0), Ok(7));
+/// assert_eq!(parse_digit::<u8>('f', 16, 0), Ok(15));
+/// assert_eq!(
+///     parse_digit::<u8>('9', 8, 3),
+///     Err(DigitError::InvalidDigit { c: '9', position: 3, radix: 8 })
+/// );
+/// ```
+pub fn parse_digit<T: Integer>(c: char, radix: u32, position: usize) -> Result<T, DigitError> {
+    radix_of::<T>(radix);
+    c.to_digit(radix)
+        .map(|digit| T::from_u8(digit as u8))
+        .ok_or(DigitError::InvalidDigit { c, position, radix })
+}
+
+/// Parse a string of digit characters in base `radix` (no sign or prefix), reporting the
+/// position of the first invalid digit.
+///
+/// ```
+/// # use util::digits::{DigitError, parse_digits};
+/// assert_eq!(parse_digits::<u32>("987", 10), Ok(987));
+/// assert_eq!(parse_digits::<u8>("11111111", 2), Ok(255));
+/// assert_eq!(parse_digits::<u8>("256", 10), Err(DigitError::Overflow));
+/// assert_eq!(parse_digits::<u8>("", 10), Err(DigitError::Empty));
+/// assert!(matches!(
+///     parse_digits::<u32>("12x4", 10),
+///     Err(DigitError::InvalidDigit { position: 2, .. })
+/// ));
+/// ```
+pub fn parse_digits<T: Integer>(s: &str, radix: u32) -> Result<T, DigitError> {
+    if s.is_empty() {
+        return Err(DigitError::Empty);
+    }
+    s.chars().enumerate().try_fold(T::ZERO, |n, (position, c)| {
+        let digit = parse_digit(c, radix, position)?;
+        n.checked_mul(radix_of(radix))
+            .and_then(|n| n.checked_add(digit))
+            .ok_or(DigitError::Overflow)
+    })
+}
@@ -0,0 +1,453 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a search knows about a node it reached.
+#[derive(Debug, Clone)]
+struct Reached<N, C> {
+    cost: C,
+
+    /// How many distinct shortest paths reach the node, saturating at `u128::MAX`.
+    paths: u128,
+
+    /// The node before this one on a shortest path, or `None` for a start node.
+    parent: Option<N>,
+}
+
+/// Shortest paths from a set of start nodes to every node they can reach, as found by [`bfs`] or
+/// [`dijkstra`].
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N, C> {
+    reached: HashMap<N, Reached<N, C>>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> ShortestPaths<N, C> {
+    /// The cost of the shortest path to `node`, or `None` if it wasn't reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.reached.get(node).map(|reached| reached.cost)
+    }
+
+    /// Whether `node` was reached.
+    pub fn contains(&self, node: &N) -> bool {
+        self.reached.contains_key(node)
+    }
+
+    /// How many distinct shortest paths reach `node` from any start node, saturating at
+    /// `u128::MAX`. Zero if `node` wasn't reached.
+    ///
+    /// Exact when every edge has a positive cost (always true for [`bfs`]).
+    pub fn path_count(&self, node: &N) -> u128 {
+        self.reached.get(node).map_or(0, |reached| reached.paths)
+    }
+
+    /// One shortest path from a start node to `node`, including both, or `None` if `node` wasn't
+    /// reached.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        let mut path = vec![node.clone()];
+        let mut reached = self.reached.get(node)?;
+        while let Some(parent) = &reached.parent {
+            path.push(parent.clone());
+            reached = &self.reached[parent];
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Iterate over every reached node and the cost of its shortest path, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> + '_ {
+        self.reached
+            .iter()
+            .map(|(node, reached)| (node, reached.cost))
+    }
+
+    /// How many nodes were reached, including the start nodes.
+    pub fn len(&self) -> usize {
+        self.reached.len()
+    }
+
+    /// Whether no nodes were reached (i.e. there were no start nodes).
+    pub fn is_empty(&self) -> bool {
+        self.reached.is_empty()
+    }
+}
+
+/// Insert each of `starts` as a zero-cost start node.
+fn start_nodes<N: Clone + Eq + Hash, C: Default>(
+    starts: impl IntoIterator<Item = N>,
+) -> HashMap<N, Reached<N, C>> {
+    let mut reached = HashMap::new();
+    for start in starts {
+        reached.entry(start).or_insert(Reached {
+            cost: C::default(),
+            paths: 1,
+            parent: None,
+        });
+    }
+    reached
+}
+
+/// Breadth-first search from every node in `starts` at once, where each edge costs 1. `neighbors`
+/// returns the nodes reachable in one step from a node, so this works on grids, adjacency lists
+/// and implicit state spaces alike.
+///
+/// ```
+/// # use util::graph::bfs;
+/// # use util::grid::{Grid, Point};
+/// let maze = Grid::parse("S.#\n..#\n#.E\n", |c| Some(c)).unwrap();
+/// let start = maze.find(&'S').unwrap();
+/// let end = maze.find(&'E').unwrap();
+///
+/// let paths = bfs([start], |point| {
+///     maze.neighbors4(*point)
+///         .filter(|neighbor| maze[*neighbor] != '#')
+///         .collect::<Vec<_>>()
+/// });
+/// assert_eq!(paths.cost(&end), Some(4));
+/// assert_eq!(paths.path(&end).unwrap().len(), 5);
+/// // Around either side of the top-left corner.
+/// assert_eq!(paths.path_count(&end), 2);
+/// assert_eq!(paths.path_count(&Point::new(1, 1)), 2);
+/// assert!(!paths.contains(&Point::new(0, 2)));
+/// ```
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> ShortestPaths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = start_nodes(starts);
+    let mut queue: VecDeque<N> = reached.keys().cloned().collect();
+    while let Some(node) = queue.pop_front() {
+        let (cost, paths) = (reached[&node].cost + 1, reached[&node].paths);
+        for neighbor in neighbors(&node) {
+            match reached.entry(neighbor) {
+                Entry::Vacant(entry) => {
+                    queue.push_back(entry.key().clone());
+                    entry.insert(Reached {
+                        cost,
+                        paths,
+                        parent: Some(node.clone()),
+                    });
+                }
+                // Every node at the previous distance is dequeued before this one, so `paths` is
+                // already final.
+                Entry::Occupied(mut entry) if entry.get().cost == cost => {
+                    let other = entry.get_mut();
+                    other.paths = other.paths.saturating_add(paths);
+                }
+                Entry::Occupied(_) => {}
+            }
+        }
+    }
+    ShortestPaths { reached }
+}
+
+/// Dijkstra's algorithm from every node in `starts` at once. `neighbors` returns the nodes
+/// reachable in one step from a node along with the cost of that step. Costs must not be
+/// negative.
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use util::graph::dijkstra;
+/// let edges = HashMap::from([
+///     ("a", vec![("b", 7), ("c", 2)]),
+///     ("b", vec![("d", 1)]),
+///     ("c", vec![("b", 3), ("d", 6)]),
+/// ]);
+/// let paths = dijkstra(["a"], |node| edges.get(node).cloned().unwrap_or_default());
+/// assert_eq!(paths.cost(&"d"), Some(6));
+/// assert_eq!(paths.path(&"d"), Some(vec!["a", "c", "b", "d"]));
+/// assert_eq!(paths.path_count(&"d"), 1);
+/// ```
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> ShortestPaths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut reached = start_nodes(starts);
+    let mut queue = PriorityQueue::new();
+    for (node, start) in reached.iter() {
+        queue.push(start.cost, node.clone());
+    }
+    let mut done = HashSet::new();
+    while let Some((cost, node)) = queue.pop() {
+        if reached[&node].cost < cost || !done.insert(node.clone()) {
+            continue;
+        }
+        let paths = reached[&node].paths;
+        for (neighbor, step_cost) in neighbors(&node) {
+            let cost = cost + step_cost;
+            match reached.entry(neighbor) {
+                Entry::Vacant(entry) => {
+                    queue.push(cost, entry.key().clone());
+                    entry.insert(Reached {
+                        cost,
+                        paths,
+                        parent: Some(node.clone()),
+                    });
+                }
+                Entry::Occupied(mut entry) if cost < entry.get().cost => {
+                    queue.push(cost, entry.key().clone());
+                    entry.insert(Reached {
+                        cost,
+                        paths,
+                        parent: Some(node.clone()),
+                    });
+                }
+                Entry::Occupied(mut entry) if cost == entry.get().cost => {
+                    let other = entry.get_mut();
+                    other.paths = other.paths.saturating_add(paths);
+                }
+                Entry::Occupied(_) => {}
+            }
+        }
+    }
+    ShortestPaths { reached }
+}
+
+/// A* search from every node in `starts` to the first node for which `is_goal` returns `true`.
+/// Returns the path (including its start and goal) and its cost, or `None` if no goal is
+/// reachable.
+///
+/// `heuristic` estimates the remaining cost from a node to the nearest goal. It must never
+/// overestimate, or the path found may not be the shortest. If it also never drops by more than
+/// the cost of a step (e.g. Manhattan distance on a grid), each node is expanded at most once;
+/// otherwise nodes are expanded again whenever a cheaper path to them is found.
+///
+/// ```
+/// # use util::graph::astar;
+/// # use util::grid::{Grid, Point};
+/// let maze = Grid::parse("S...\n.##.\n...E\n", |c| Some(c)).unwrap();
+/// let (start, end) = (maze.find(&'S').unwrap(), maze.find(&'E').unwrap());
+///
+/// let (path, cost) = astar(
+///     [start],
+///     |point| {
+///         maze.neighbors4(*point)
+///             .filter(|neighbor| maze[*neighbor] != '#')
+///             .map(|neighbor| (neighbor, 1))
+///             .collect::<Vec<_>>()
+///     },
+///     |point| point.row.abs_diff(end.row) + point.col.abs_diff(end.col),
+///     |point| *point == end,
+/// )
+/// .unwrap();
+/// assert_eq!(cost, 5);
+/// assert_eq!(path.first(), Some(&start));
+/// assert_eq!(path.last(), Some(&end));
+/// ```
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut reached = start_nodes(starts);
+    let mut queue = PriorityQueue::new();
+    for (node, start) in reached.iter() {
+        queue.push(start.cost + heuristic(node), (node.clone(), start.cost));
+    }
+    while let Some((_, (node, cost))) = queue.pop() {
+        // Skip nodes which were reached more cheaply after being queued.
+        if reached[&node].cost < cost {
+            continue;
+        }
+        if is_goal(&node) {
+            let paths = ShortestPaths { reached };
+            return paths.path(&node).map(|path| (path, cost));
+        }
+        for (neighbor, step_cost) in neighbors(&node) {
+            let cost = cost + step_cost;
+            if reached
+                .get(&neighbor)
+                .is_some_and(|other| other.cost <= cost)
+            {
+                continue;
+            }
+            queue.push(cost + heuristic(&neighbor), (neighbor.clone(), cost));
+            reached.insert(
+                neighbor,
+                Reached {
+                    cost,
+                    paths: 1,
+                    parent: Some(node.clone()),
+                },
+            );
+        }
+    }
+    None
+}
+
+/// A min-priority queue which doesn't require nodes to be [`Ord`]. Nodes with equal priority are
+/// popped in the order they were pushed.
+struct PriorityQueue<N, C> {
+    heap: BinaryHeap<Reverse<(C, usize)>>,
+    nodes: HashMap<usize, N>,
+    pushed: usize,
+}
+
+impl<N, C: Ord> PriorityQueue<N, C> {
+    fn new() -> PriorityQueue<N, C> {
+        PriorityQueue {
+            heap: BinaryHeap::new(),
+            nodes: HashMap::new(),
+            pushed: 0,
+        }
+    }
+
+    fn push(&mut self, priority: C, node: N) {
+        self.heap.push(Reverse((priority, self.pushed)));
+        self.nodes.insert(self.pushed, node);
+        self.pushed += 1;
+    }
+
+    fn pop(&mut self) -> Option<(C, N)> {
+        let Reverse((priority, id)) = self.heap.pop()?;
+        self.nodes.remove(&id).map(|node| (priority, node))
+    }
+}
+
+/// Group `nodes` into connected components of the nodes reachable from each other through
+/// `neighbors`. Components are returned in the order their first node appears in `nodes`, and
+/// include nodes reached through `neighbors` even if they aren't in `nodes`.
+///
+/// Edges are only followed forwards, so `neighbors` should be symmetric (as in an undirected
+/// graph). Otherwise, each component is just the nodes not already in an earlier component which
+/// are reachable from its first node.
+///
+/// ```
+/// # use util::graph::connected_components;
+/// # use util::grid::Grid;
+/// let grid = Grid::parse("AAB\nCAB\nCCB\n", |c| Some(c)).unwrap();
+/// let regions = connected_components(grid.points(), |point| {
+///     grid.neighbors4(*point)
+///         .filter(|neighbor| grid[*neighbor] == grid[*point])
+///         .collect::<Vec<_>>()
+/// });
+/// let sizes: Vec<_> = regions.iter().map(|region| region.len()).collect();
+/// assert_eq!(sizes, vec![3, 3, 3]);
+/// ```
+pub fn connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut components = vec![];
+    for node in nodes {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+        let mut component = vec![];
+        let mut queue = VecDeque::from([node]);
+        while let Some(node) = queue.pop_front() {
+            for neighbor in neighbors(&node) {
+                if seen.insert(neighbor.clone()) {
+                    queue.push_back(neighbor);
+                }
+            }
+            component.push(node);
+        }
+        components.push(component);
+    }
+    components
+}
+
+/// Returned by [`toposort`] when the graph isn't acyclic.
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+#[error("graph has a cycle: {cycle:?}")]
+pub struct CycleError<N: Debug> {
+    /// The nodes of one cycle in order, starting and ending with the same node.
+    pub cycle: Vec<N>,
+}
+
+/// Whether a node's successors are still being visited by [`toposort`].
+#[derive(PartialEq)]
+enum Visit {
+    InProgress,
+    Done,
+}
+
+/// Sort `nodes` and everything reachable from them so that every node comes before its
+/// `successors`. Returns a [`CycleError`] describing one cycle if that's impossible.
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use util::graph::toposort;
+/// let steps = HashMap::from([('C', vec!['A', 'F']), ('A', vec!['B', 'D']), ('B', vec!['E'])]);
+/// let successors = |step: &char| steps.get(step).cloned().unwrap_or_default();
+/// let order = toposort(['C'], successors).unwrap();
+/// assert_eq!(order.first(), Some(&'C'));
+/// assert!(order.iter().position(|s| *s == 'B') < order.iter().position(|s| *s == 'E'));
+///
+/// let cyclic = HashMap::from([(1, vec![2]), (2, vec![3]), (3, vec![1])]);
+/// let error = toposort([1], |n| cyclic[n].clone()).unwrap_err();
+/// assert_eq!(error.cycle, vec![1, 2, 3, 1]);
+/// ```
+pub fn toposort<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Result<Vec<N>, CycleError<N>>
+where
+    N: Clone + Eq + Hash + Debug,
+    I: IntoIterator<Item = N>,
+{
+    let mut visits = HashMap::new();
+    let mut postorder = vec![];
+    for node in nodes {
+        if visits.contains_key(&node) {
+            continue;
+        }
+        // Depth-first search with an explicit stack of nodes and their remaining successors.
+        visits.insert(node.clone(), Visit::InProgress);
+        let successors_of_node = successors(&node).into_iter();
+        let mut stack = vec![(node, successors_of_node)];
+        while let Some((node, remaining)) = stack.last_mut() {
+            let Some(next) = remaining.next() else {
+                visits.insert(node.clone(), Visit::Done);
+                postorder.push(node.clone());
+                stack.pop();
+                continue;
+            };
+            match visits.get(&next) {
+                Some(Visit::Done) => {}
+                Some(Visit::InProgress) => {
+                    let cycle_start = stack
+                        .iter()
+                        .position(|(node, _)| *node == next)
+                        .unwrap_or_default();
+                    let mut cycle: Vec<N> = stack[cycle_start..]
+                        .iter()
+                        .map(|(node, _)| node.clone())
+                        .collect();
+                    cycle.push(next);
+                    return Err(CycleError { cycle });
+                }
+                None => {
+                    visits.insert(next.clone(), Visit::InProgress);
+                    let successors_of_next = successors(&next).into_iter();
+                    stack.push((next, successors_of_next));
+                }
+            }
+        }
+    }
+    postorder.reverse();
+    Ok(postorder)
+}
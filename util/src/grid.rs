@@ -0,0 +1,440 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// A cell's coordinates in a [`Grid`]. Rows count down from the top and columns count right from
+/// the left, both starting at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Point {
+    pub fn new(row: usize, col: usize) -> Point {
+        Point { row, col }
+    }
+
+    /// The point one step away in direction `adj`, or `None` if that would be above the top row
+    /// or left of the first column. Doesn't check the other edges of any grid.
+    pub fn step(self, adj: Adjacency) -> Option<Point> {
+        let (row_offset, col_offset) = adj.offset();
+        Some(Point {
+            row: self.row.checked_add_signed(row_offset)?,
+            col: self.col.checked_add_signed(col_offset)?,
+        })
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((row, col): (usize, usize)) -> Point {
+        Point { row, col }
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+/// The eight adjacencies that each cell may have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Adjacency {
+    TopLeft,
+    Top,
+    TopRight,
+    Right,
+    BottomRight,
+    Bottom,
+    BottomLeft,
+    Left,
+}
+
+impl Adjacency {
+    /// Every adjacency, clockwise from [`Adjacency::TopLeft`].
+    pub const ALL: [Adjacency; 8] = [
+        Adjacency::TopLeft,
+        Adjacency::Top,
+        Adjacency::TopRight,
+        Adjacency::Right,
+        Adjacency::BottomRight,
+        Adjacency::Bottom,
+        Adjacency::BottomLeft,
+        Adjacency::Left,
+    ];
+
+    /// The adjacencies which share an edge with a cell, clockwise from [`Adjacency::Top`].
+    pub const ORTHOGONAL: [Adjacency; 4] = [
+        Adjacency::Top,
+        Adjacency::Right,
+        Adjacency::Bottom,
+        Adjacency::Left,
+    ];
+
+    /// How far a step in this direction moves, as `(rows, cols)`.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Adjacency::TopLeft => (-1, -1),
+            Adjacency::Top => (-1, 0),
+            Adjacency::TopRight => (-1, 1),
+            Adjacency::Right => (0, 1),
+            Adjacency::BottomRight => (1, 1),
+            Adjacency::Bottom => (1, 0),
+            Adjacency::BottomLeft => (1, -1),
+            Adjacency::Left => (0, -1),
+        }
+    }
+
+    /// The adjacency pointing the other way, e.g. [`Adjacency::Bottom`] for [`Adjacency::Top`].
+    pub fn opposite(self) -> Adjacency {
+        match self {
+            Adjacency::TopLeft => Adjacency::BottomRight,
+            Adjacency::Top => Adjacency::Bottom,
+            Adjacency::TopRight => Adjacency::BottomLeft,
+            Adjacency::Right => Adjacency::Left,
+            Adjacency::BottomRight => Adjacency::TopLeft,
+            Adjacency::Bottom => Adjacency::Top,
+            Adjacency::BottomLeft => Adjacency::TopRight,
+            Adjacency::Left => Adjacency::Right,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum GridError {
+    #[error("row {row} has {len} columns; expected {expected}")]
+    RaggedRow {
+        row: usize,
+        len: usize,
+        expected: usize,
+    },
+
+    #[error("unexpected character {c:?} at {point}")]
+    InvalidChar { point: Point, c: char },
+}
+
+/// A rectangular 2D grid, stored row by row.
+///
+/// ```
+/// # use util::grid::{Grid, Point};
+/// let grid = Grid::parse("..@\n@@.\n", |c| Some(c == '@')).unwrap();
+/// assert_eq!((grid.rows(), grid.cols()), (2, 3));
+/// assert_eq!(grid[(0, 2)], true);
+/// assert_eq!(grid.get(Point::new(1, 2)), Some(&false));
+/// assert_eq!(grid.get((2, 0)), None);
+///
+/// let rolls: Vec<_> = grid.find_all(&true).collect();
+/// assert_eq!(rolls, vec![Point::new(0, 2), Point::new(1, 0), Point::new(1, 1)]);
+///
+/// let neighboring_rolls = grid.neighbors8((0, 1)).filter(|p| grid[*p]).count();
+/// assert_eq!(neighboring_rolls, 3);
+///
+/// let rotated = grid.rotate_clockwise();
+/// let rendered = rotated.display(|roll| if *roll { '@' } else { '.' }).to_string();
+/// assert_eq!(rendered, "@.\n@.\n.@\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    /// Every cell, row by row.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from its cells, row by row. Panics unless there are `rows * cols` cells.
+    pub fn from_cells(rows: usize, cols: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(
+            rows * cols,
+            cells.len(),
+            "grid size doesn't match cell count"
+        );
+        Grid { rows, cols, cells }
+    }
+
+    /// Parse a grid from lines of characters, mapping each character to a cell with `f`. `f`
+    /// returns `None` for characters which shouldn't appear in the grid.
+    ///
+    /// Every line must have the same number of characters. An empty input is a 0x0 grid.
+    pub fn from_lines<S: AsRef<str>>(
+        lines: impl IntoIterator<Item = S>,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, GridError> {
+        let mut rows = 0;
+        let mut cols = None;
+        let mut cells = vec![];
+        for (row, line) in lines.into_iter().enumerate() {
+            let before = cells.len();
+            for (col, c) in line.as_ref().chars().enumerate() {
+                let point = Point::new(row, col);
+                cells.push(f(c).ok_or(GridError::InvalidChar { point, c })?);
+            }
+            let len = cells.len() - before;
+            match cols {
+                Some(expected) if expected != len => {
+                    return Err(GridError::RaggedRow { row, len, expected });
+                }
+                _ => cols = Some(len),
+            }
+            rows += 1;
+        }
+        Ok(Grid {
+            rows,
+            cols: cols.unwrap_or_default(),
+            cells,
+        })
+    }
+
+    /// Parse a grid from text; see [`Grid::from_lines`].
+    pub fn parse(text: &str, f: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, GridError> {
+        Grid::from_lines(text.lines(), f)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Whether the grid has no cells.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether `point` is inside the grid.
+    pub fn contains(&self, point: impl Into<Point>) -> bool {
+        let point = point.into();
+        point.row < self.rows && point.col < self.cols
+    }
+
+    fn idx(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then_some(point.row * self.cols + point.col)
+    }
+
+    /// Get the cell at `point` (a [`Point`] or `(row, col)`), or `None` if it's outside the grid.
+    pub fn get(&self, point: impl Into<Point>) -> Option<&T> {
+        self.idx(point.into()).map(|idx| &self.cells[idx])
+    }
+
+    /// Get a mutable reference to the cell at `point`, or `None` if it's outside the grid.
+    pub fn get_mut(&mut self, point: impl Into<Point>) -> Option<&mut T> {
+        self.idx(point.into()).map(|idx| &mut self.cells[idx])
+    }
+
+    /// The point one step from `point` in direction `adj`, or `None` if it's outside the grid.
+    pub fn adjacent(&self, point: impl Into<Point>, adj: Adjacency) -> Option<Point> {
+        point
+            .into()
+            .step(adj)
+            .filter(|adjacent| self.contains(*adjacent))
+    }
+
+    /// Iterate over the points next to `point` in each of `directions` which are inside the grid.
+    pub fn neighbors<'a>(
+        &'a self,
+        point: impl Into<Point>,
+        directions: &'a [Adjacency],
+    ) -> impl Iterator<Item = Point> + 'a {
+        let point = point.into();
+        directions
+            .iter()
+            .filter_map(move |adj| self.adjacent(point, *adj))
+    }
+
+    /// Iterate over the up to 4 points which share an edge with `point`.
+    pub fn neighbors4(&self, point: impl Into<Point>) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(point, &Adjacency::ORTHOGONAL)
+    }
+
+    /// Iterate over the up to 8 points which share an edge or corner with `point`.
+    pub fn neighbors8(&self, point: impl Into<Point>) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(point, &Adjacency::ALL)
+    }
+
+    /// Iterate over the points from `start` (inclusive) to the edge of the grid in direction
+    /// `adj`, along with their cells.
+    pub fn ray(
+        &self,
+        start: impl Into<Point>,
+        adj: Adjacency,
+    ) -> impl Iterator<Item = (Point, &T)> + '_ {
+        let start = Some(start.into()).filter(|start| self.contains(*start));
+        std::iter::successors(start, move |point| self.adjacent(*point, adj))
+            .map(|point| (point, &self[point]))
+    }
+
+    /// A row's cells, left to right. Empty if `row` is outside the grid.
+    pub fn row(&self, row: usize) -> &[T] {
+        if row >= self.rows {
+            return &[];
+        }
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// A column's cells, top to bottom. Empty if `col` is outside the grid.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        self.ray((0, col), Adjacency::Bottom).map(|(_, cell)| cell)
+    }
+
+    /// The cells on the diagonal running down and to the right from `start`.
+    pub fn diagonal(&self, start: impl Into<Point>) -> impl Iterator<Item = &T> + '_ {
+        self.ray(start, Adjacency::BottomRight)
+            .map(|(_, cell)| cell)
+    }
+
+    /// The cells on the diagonal running down and to the left from `start`.
+    pub fn anti_diagonal(&self, start: impl Into<Point>) -> impl Iterator<Item = &T> + '_ {
+        self.ray(start, Adjacency::BottomLeft).map(|(_, cell)| cell)
+    }
+
+    /// Iterate over every point left to right, top to bottom.
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let cols = self.cols;
+        (0..self.cells.len()).map(move |i| Point::new(i / cols, i % cols))
+    }
+
+    /// Iterate over every cell left to right, top to bottom, along with its point.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// Iterate mutably over every cell left to right, top to bottom, along with its point.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> + '_ {
+        self.points().zip(self.cells.iter_mut())
+    }
+
+    /// Iterate over the points of every cell equal to `value`, left to right, top to bottom.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(point, _)| point)
+    }
+
+    /// Return the point of the first cell equal to `value`, scanning left to right, top to bottom.
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    /// Create a grid of the same size by mapping each cell with `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Render the grid one row per line, turning each cell into a character with `f`.
+    pub fn display<F: Fn(&T) -> char>(&self, f: F) -> GridDisplay<'_, T, F> {
+        GridDisplay { grid: self, f }
+    }
+
+    /// Create a `rows` x `cols` grid where each point's cell is cloned from `source(point)`.
+    fn rearrange(&self, rows: usize, cols: usize, source: impl Fn(Point) -> Point) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..rows * cols)
+            .map(|i| self[source(Point::new(i / cols, i % cols))].clone())
+            .collect();
+        Grid { rows, cols, cells }
+    }
+
+    /// Swap rows and columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearrange(self.cols, self.rows, |p| Point::new(p.col, p.row))
+    }
+
+    /// Rotate the grid a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let rows = self.rows;
+        self.rearrange(self.cols, self.rows, |p| {
+            Point::new(rows - 1 - p.col, p.row)
+        })
+    }
+
+    /// Rotate the grid a quarter turn counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cols = self.cols;
+        self.rearrange(self.cols, self.rows, |p| {
+            Point::new(p.col, cols - 1 - p.row)
+        })
+    }
+
+    /// Mirror the grid left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cols = self.cols;
+        self.rearrange(self.rows, self.cols, |p| {
+            Point::new(p.row, cols - 1 - p.col)
+        })
+    }
+
+    /// Mirror the grid top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let rows = self.rows;
+        self.rearrange(self.rows, self.cols, |p| {
+            Point::new(rows - 1 - p.row, p.col)
+        })
+    }
+}
+
+impl<T, P: Into<Point>> Index<P> for Grid<T> {
+    type Output = T;
+
+    /// Panics if `point` is outside the grid; see [`Grid::get`] for a checked alternative.
+    fn index(&self, point: P) -> &T {
+        let point = point.into();
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point} is outside the grid"))
+    }
+}
+
+impl<T, P: Into<Point>> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, point: P) -> &mut T {
+        let point = point.into();
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point} is outside the grid"))
+    }
+}
+
+/// Renders a [`Grid`] through a closure; see [`Grid::display`].
+pub struct GridDisplay<'a, T, F> {
+    grid: &'a Grid<T>,
+    f: F,
+}
+
+impl<T, F: Fn(&T) -> char> Display for GridDisplay<'_, T, F> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for row in 0..self.grid.rows {
+            for cell in self.grid.row(row) {
+                write!(f, "{}", (self.f)(cell))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
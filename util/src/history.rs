@@ -0,0 +1,185 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::runner::{RunReport, Verdict};
+
+/// A single run of a solution, as recorded in `dayX/history.jsonl`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HistoryRecord {
+    /// When the run finished, in seconds since the Unix epoch.
+    pub timestamp: u64,
+
+    /// The problem that was run (e.g. `day1-2`).
+    pub problem: String,
+
+    /// The custom input name (e.g. `test`), or `None` for the default input.
+    pub input: Option<String>,
+
+    /// The solution's output.
+    pub answer: String,
+
+    /// How long the solution function took to run, in seconds.
+    pub duration_secs: f64,
+
+    /// Whether the answer was known to be correct at the time it was produced.
+    pub verdict: Verdict,
+
+    /// The git commit the solution was built from (e.g. `8c91fff` or `8c91fff-dirty`), if known.
+    pub commit: Option<String>,
+}
+
+impl HistoryRecord {
+    /// Create a [`HistoryRecord`] for a run that just finished.
+    pub fn new(report: &RunReport, input: &Option<String>, working_dir: &Path) -> HistoryRecord {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        HistoryRecord {
+            timestamp,
+            problem: report.problem.to_string(),
+            input: input.clone(),
+            answer: report.solution.clone(),
+            duration_secs: report.duration.as_secs_f64(),
+            verdict: report.verdict,
+            commit: git_commit(working_dir),
+        }
+    }
+
+    /// How long the solution function took to run.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.duration_secs)
+    }
+}
+
+/// Return the current git commit for `working_dir`, suffixed with `-dirty` if the working tree has
+/// uncommitted changes. Returns `None` if `git` isn't available or this isn't a git repository.
+pub fn git_commit(working_dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty", "--exclude", "*"])
+        .current_dir(working_dir)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|commit| !commit.is_empty())
+}
+
+/// Append `record` to the history file at `filepath` as a single line of JSON.
+pub fn append<P: AsRef<Path>>(filepath: P, record: &HistoryRecord) -> anyhow::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(filepath)?;
+    serde_json::to_writer(&mut file, record)?;
+    writeln!(file)?;
+    Ok(())
+}
+
+/// Read every record from the history file at `filepath`, oldest first. Lines which can't be
+/// parsed are skipped with a warning. A missing history file has no records.
+pub fn load<P: AsRef<Path>>(filepath: P) -> anyhow::Result<Vec<HistoryRecord>> {
+    let file = match File::open(filepath) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+    let reader = BufReader::new(file);
+    let mut records = vec![];
+    for (i, line) in reader.lines().enumerate() {
+        match serde_json::from_str(&line?) {
+            Ok(record) => records.push(record),
+            Err(e) => tracing::warn!("Skipping malformed history record on line {}: {e}", i + 1),
+        }
+    }
+    Ok(records)
+}
+
+/// Consecutive runs that were built from the same commit and produced the same answer.
+pub struct HistoryEntry<'a> {
+    pub commit: Option<&'a str>,
+    pub answer: &'a str,
+    pub verdict: Verdict,
+    pub runs: usize,
+    pub fastest: Duration,
+    pub slowest: Duration,
+}
+
+/// Collapse `records` into [`HistoryEntry`]s so that each entry marks a change in commit or
+/// answer. Only records for `problem` and `input` are considered.
+pub fn summarize<'a>(
+    records: &'a [HistoryRecord],
+    problem: &str,
+    input: &Option<String>,
+) -> Vec<HistoryEntry<'a>> {
+    let mut entries: Vec<HistoryEntry> = vec![];
+    for record in records
+        .iter()
+        .filter(|record| record.problem == problem && &record.input == input)
+    {
+        let duration = record.duration();
+        match entries.last_mut() {
+            Some(entry)
+                if entry.commit == record.commit.as_deref() && entry.answer == record.answer =>
+            {
+                entry.runs += 1;
+                entry.verdict = record.verdict;
+                entry.fastest = entry.fastest.min(duration);
+                entry.slowest = entry.slowest.max(duration);
+            }
+            _ => entries.push(HistoryEntry {
+                commit: record.commit.as_deref(),
+                answer: &record.answer,
+                verdict: record.verdict,
+                runs: 1,
+                fastest: duration,
+                slowest: duration,
+            }),
+        }
+    }
+    entries
+}
+
+/// Print a table showing how `problem`'s answer and runtime evolved over time.
+pub fn print_history(
+    records: &[HistoryRecord],
+    problem: &str,
+    input: &Option<String>,
+) -> anyhow::Result<()> {
+    let entries = summarize(records, problem, input);
+    if entries.is_empty() {
+        tracing::warn!("No history recorded for {problem}");
+        return Ok(());
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    writeln!(
+        stdout,
+        "{:<16} {:>5} {:>12} {:>12} {:<10} ANSWER",
+        "COMMIT", "RUNS", "FASTEST", "SLOWEST", "VERDICT"
+    )?;
+    let mut previous_answer = None;
+    for entry in entries.iter() {
+        let changed = previous_answer.is_some_and(|previous| previous != entry.answer);
+        writeln!(
+            stdout,
+            "{:<16} {:>5} {:>12} {:>12} {:<10} {}{}",
+            entry.commit.unwrap_or("unknown"),
+            entry.runs,
+            format!("{:.2?}", entry.fastest),
+            format!("{:.2?}", entry.slowest),
+            entry.verdict.to_string(),
+            entry.answer,
+            if changed { "  <- answer changed" } else { "" },
+        )?;
+        previous_answer = Some(entry.answer);
+    }
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use std::error::Error;
+use std::io::{self, BufRead, Lines};
+use std::str::FromStr;
+
+/// The result of [`normalize`]ing an input file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Normalized {
+    /// The normalized input.
+    pub text: String,
+
+    /// How many `\r\n` line endings were replaced with `\n`.
+    pub crlf_line_endings: usize,
+
+    /// How many blank (or whitespace-only) lines were removed from the end of the input.
+    pub trailing_blank_lines: usize,
+}
+
+impl Normalized {
+    /// Whether normalizing changed the input.
+    pub fn changed(&self) -> bool {
+        self.crlf_line_endings > 0 || self.trailing_blank_lines > 0
+    }
+}
+
+/// Replace `\r\n` line endings with `\n` and remove blank lines from the end of `text`. If `text`
+/// ended with a newline, the normalized text still does.
+///
+/// Inputs saved on Windows or pasted from a browser can have either, which breaks parsers that
+/// slice lines by byte position or use the first line's length as a grid's width.
+///
+/// ```
+/// # use util::input::normalize;
+/// let normalized = normalize("..@\r\n@.@\r\n\r\n  \n");
+/// assert_eq!(normalized.text, "..@\n@.@\n");
+/// assert_eq!(normalized.crlf_line_endings, 3);
+/// assert_eq!(normalized.trailing_blank_lines, 2);
+///
+/// let unchanged = normalize("..@\n@.@");
+/// assert_eq!(unchanged.text, "..@\n@.@");
+/// assert!(!unchanged.changed());
+/// ```
+pub fn normalize(text: &str) -> Normalized {
+    let crlf_line_endings = text.matches("\r\n").count();
+    let mut lines: Vec<&str> = text.lines().collect();
+    let content_lines = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |last| last + 1);
+    let trailing_blank_lines = lines.len() - content_lines;
+    lines.truncate(content_lines);
+
+    let mut normalized = lines.join("\n");
+    if text.ends_with('\n') && !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    Normalized {
+        text: normalized,
+        crlf_line_endings,
+        trailing_blank_lines,
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SectionError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    /// The input ended before the given section, counting from 1.
+    #[error("expected section {0}, but the input ended")]
+    Missing(usize),
+
+    #[error("cannot parse section {section}: {source}")]
+    Parse {
+        section: usize,
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
+
+/// One blank-line-separated block of an input, read by [`Sections`]. The default is an empty
+/// block, for sections which may be left out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Section {
+    text: String,
+}
+
+impl Section {
+    /// The block's lines joined with `\n`, without a trailing newline.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+}
+
+/// Read an input one blank-line-separated block at a time, e.g. a list of ranges followed by a
+/// list of queries. Runs of blank (or whitespace-only) lines count as a single separator, and
+/// blank lines at the start or end of the input are ignored.
+///
+/// ```
+/// # use util::input::Sections;
+/// let input = "3-5\n10-14\n\n1\n5\n8\n";
+/// let mut sections = Sections::new(input.as_bytes());
+///
+/// let ranges = sections.next_section().unwrap();
+/// assert_eq!(ranges.lines().collect::<Vec<_>>(), ["3-5", "10-14"]);
+///
+/// let queries: Vec<u64> = sections
+///     .next_section()
+///     .unwrap()
+///     .lines()
+///     .map(|line| line.parse().unwrap())
+///     .collect();
+/// assert_eq!(queries, [1, 5, 8]);
+///
+/// assert!(sections.next().is_none());
+/// ```
+///
+/// Whole sections can also be parsed with [`FromStr`]:
+///
+/// ```
+/// # use util::input::{SectionError, Sections};
+/// let mut sections = Sections::new("42\n\nnot a number".as_bytes());
+/// assert_eq!(sections.next_parsed::<u32>().unwrap(), 42);
+/// assert!(matches!(
+///     sections.next_parsed::<u32>(),
+///     Err(SectionError::Parse { section: 2, .. })
+/// ));
+/// assert!(matches!(
+///     sections.next_parsed::<u32>(),
+///     Err(SectionError::Missing(3))
+/// ));
+/// ```
+pub struct Sections<R> {
+    lines: Lines<R>,
+
+    /// How many sections have been read so far.
+    count: usize,
+}
+
+impl<R: BufRead> Sections<R> {
+    pub fn new(reader: R) -> Sections<R> {
+        Sections {
+            lines: reader.lines(),
+            count: 0,
+        }
+    }
+
+    /// Read the next section, failing if the input has already ended.
+    pub fn next_section(&mut self) -> Result<Section, SectionError> {
+        match self.next() {
+            Some(section) => Ok(section?),
+            None => Err(SectionError::Missing(self.count + 1)),
+        }
+    }
+
+    /// Read the next section and parse all of it as a `T`.
+    pub fn next_parsed<T>(&mut self) -> Result<T, SectionError>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let section = self.next_section()?;
+        section
+            .as_str()
+            .parse()
+            .map_err(|error: T::Err| SectionError::Parse {
+                section: self.count,
+                source: error.into(),
+            })
+    }
+}
+
+impl<R: BufRead> Iterator for Sections<R> {
+    type Item = io::Result<Section>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut lines = vec![];
+        for line in self.lines.by_ref() {
+            let line = match line {
+                Ok(line) => line,
+                Err(error) => return Some(Err(error)),
+            };
+            match (line.trim().is_empty(), lines.is_empty()) {
+                (true, true) => continue,
+                (true, false) => break,
+                (false, _) => lines.push(line),
+            }
+        }
+        if lines.is_empty() {
+            return None;
+        }
+        self.count += 1;
+        Some(Ok(Section {
+            text: lines.join("\n"),
+        }))
+    }
+}
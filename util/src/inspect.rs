@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+
+use crate::scan::integer_spans;
+
+/// The range of every integer found in an input, and the smallest types which can hold them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntegerSummary {
+    /// How many integers were found.
+    pub count: usize,
+
+    pub min: i128,
+
+    pub max: i128,
+
+    /// How many integers were too large for an `i128`. They aren't included in `min` or `max`.
+    pub overflowed: usize,
+}
+
+impl IntegerSummary {
+    /// The smallest unsigned and signed integer types which can hold every integer found. The
+    /// unsigned type is `None` if there are negative integers.
+    pub fn smallest_types(&self) -> (Option<&'static str>, &'static str) {
+        const UNSIGNED: [(&str, i128); 4] = [
+            ("u8", u8::MAX as i128),
+            ("u16", u16::MAX as i128),
+            ("u32", u32::MAX as i128),
+            ("u64", u64::MAX as i128),
+        ];
+        const SIGNED: [(&str, i128, i128); 4] = [
+            ("i8", i8::MIN as i128, i8::MAX as i128),
+            ("i16", i16::MIN as i128, i16::MAX as i128),
+            ("i32", i32::MIN as i128, i32::MAX as i128),
+            ("i64", i64::MIN as i128, i64::MAX as i128),
+        ];
+        let unsigned = (self.min >= 0 && self.overflowed == 0).then(|| {
+            UNSIGNED
+                .iter()
+                .find(|(_, max)| self.max <= *max)
+                .map(|(name, _)| *name)
+                .unwrap_or("u128")
+        });
+        let signed = SIGNED
+            .iter()
+            .find(|(_, min, max)| *min <= self.min && self.max <= *max && self.overflowed == 0)
+            .map(|(name, _, _)| *name)
+            .unwrap_or("i128");
+        (unsigned, signed)
+    }
+}
+
+/// Facts about an input file, to help decide how to parse it.
+///
+/// ```
+/// # use util::inspect::InputReport;
+/// let report = InputReport::new("L68\r\n-5\r\nL1000\r\n");
+/// assert_eq!(report.lines, 3);
+/// assert_eq!((report.min_line_len, report.max_line_len), (2, 5));
+/// assert!(report.is_ragged());
+/// assert!(report.trailing_newline);
+/// assert_eq!(report.crlf_lines, 3);
+/// assert_eq!(report.histogram[&'L'], 2);
+///
+/// let integers = report.integers.unwrap();
+/// assert_eq!((integers.count, integers.min, integers.max), (3, -5, 1000));
+/// assert_eq!(integers.smallest_types(), (None, "i16"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputReport {
+    pub bytes: usize,
+
+    pub lines: usize,
+
+    /// How many lines are empty (or only contain whitespace).
+    pub blank_lines: usize,
+
+    /// The length of the shortest line in bytes, excluding line endings.
+    pub min_line_len: usize,
+
+    /// The length of the longest line in bytes, excluding line endings.
+    pub max_line_len: usize,
+
+    /// How many times each character appears, excluding line endings.
+    pub histogram: BTreeMap<char, usize>,
+
+    /// Whether the last line ends with a newline.
+    pub trailing_newline: bool,
+
+    /// How many lines end with `\r\n` rather than `\n`.
+    pub crlf_lines: usize,
+
+    /// The range of every integer in the input, or `None` if there aren't any. A `-` is treated as
+    /// a sign unless it follows a letter or digit, so `3-5` is two positive integers.
+    pub integers: Option<IntegerSummary>,
+}
+
+impl InputReport {
+    pub fn new(text: &str) -> InputReport {
+        let mut report = InputReport {
+            bytes: text.len(),
+            lines: 0,
+            blank_lines: 0,
+            min_line_len: usize::MAX,
+            max_line_len: 0,
+            histogram: BTreeMap::new(),
+            trailing_newline: text.ends_with('\n'),
+            crlf_lines: 0,
+            integers: None,
+        };
+
+        for line in text.split_inclusive('\n') {
+            let line = match line.strip_suffix("\r\n") {
+                Some(line) => {
+                    report.crlf_lines += 1;
+                    line
+                }
+                None => line.strip_suffix('\n').unwrap_or(line),
+            };
+            report.lines += 1;
+            if line.trim().is_empty() {
+                report.blank_lines += 1;
+            }
+            report.min_line_len = report.min_line_len.min(line.len());
+            report.max_line_len = report.max_line_len.max(line.len());
+            for c in line.chars() {
+                *report.histogram.entry(c).or_default() += 1;
+            }
+        }
+        if report.lines == 0 {
+            report.min_line_len = 0;
+        }
+
+        report.integers = summarize_integers(text);
+        report
+    }
+
+    /// Whether lines have different lengths (e.g. the input isn't a rectangular grid).
+    pub fn is_ragged(&self) -> bool {
+        self.min_line_len != self.max_line_len
+    }
+}
+
+/// Find every integer in `text` and summarize their range.
+fn summarize_integers(text: &str) -> Option<IntegerSummary> {
+    let mut summary: Option<IntegerSummary> = None;
+    for span in integer_spans(text) {
+        let summary = summary.get_or_insert(IntegerSummary {
+            count: 0,
+            min: i128::MAX,
+            max: i128::MIN,
+            overflowed: 0,
+        });
+        summary.count += 1;
+        match span.parse::<i128>() {
+            Ok(value) => {
+                summary.min = summary.min.min(value);
+                summary.max = summary.max.max(value);
+            }
+            Err(_) => summary.overflowed += 1,
+        }
+    }
+    summary
+}
+
+impl Display for InputReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "bytes:            {}", self.bytes)?;
+        writeln!(
+            f,
+            "lines:            {} ({} blank)",
+            self.lines, self.blank_lines
+        )?;
+        writeln!(
+            f,
+            "line length:      {}..={} ({})",
+            self.min_line_len,
+            self.max_line_len,
+            if self.is_ragged() {
+                "ragged"
+            } else {
+                "rectangular"
+            }
+        )?;
+        writeln!(f, "trailing newline: {}", self.trailing_newline)?;
+        writeln!(f, "CRLF lines:       {}", self.crlf_lines)?;
+        match &self.integers {
+            Some(integers) => {
+                let (unsigned, signed) = integers.smallest_types();
+                writeln!(
+                    f,
+                    "integers:         {} in {}..={} (fits in {}{signed})",
+                    integers.count,
+                    integers.min,
+                    integers.max,
+                    unsigned.map(|name| format!("{name}, ")).unwrap_or_default(),
+                )?;
+                if integers.overflowed > 0 {
+                    writeln!(f, "                  {} overflow i128", integers.overflowed)?;
+                }
+            }
+            None => writeln!(f, "integers:         none")?,
+        }
+        writeln!(f, "characters:")?;
+        for (c, count) in self.histogram.iter() {
+            writeln!(f, "  {:<8} {count}", format!("{c:?}"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Read the input file at `filepath` and report facts about it.
+pub fn inspect<P: AsRef<Path>>(filepath: P) -> anyhow::Result<InputReport> {
+    let text = std::fs::read_to_string(filepath)?;
+    Ok(InputReport::new(&text))
+}
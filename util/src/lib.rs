@@ -1,53 +1,193 @@
 use std::fmt::{self, Display, Formatter};
-use std::fs::File;
-use std::io::BufReader;
 use std::path::PathBuf;
 use std::str::FromStr;
 
 pub mod aoc;
+pub mod automaton;
+pub mod bitset;
+pub mod cache;
 pub mod cli;
+pub mod columns;
+pub mod compare;
+pub mod digits;
+pub mod graph;
+pub mod grid;
+pub mod history;
+pub mod input;
+pub mod inspect;
+pub mod logging;
+pub mod math;
+pub mod mem;
 pub mod range;
 pub mod runner;
+pub mod scan;
+pub mod submit;
+pub mod testing;
+pub mod timing;
+pub mod watch;
 
-#[derive(thiserror::Error, Debug)]
-#[error("failed to parse problem: {0}")]
-pub struct ParseProblemError(String);
+/// The Advent of Code event these solutions are for.
+pub const YEAR: u16 = 2025;
+
+/// How many days of problems [`YEAR`]'s event has.
+pub const DAYS: u8 = 12;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ParseProblemError {
+    #[error("`{0}` is not a problem name like `day1-2`")]
+    InvalidFormat(String),
+
+    #[error("invalid day `{0}`: expected a number from 1 to {DAYS}")]
+    InvalidDay(String),
+
+    #[error("invalid part `{0}`: expected 1 or 2")]
+    InvalidPart(String),
+}
+
+/// A day of [`YEAR`]'s event, from 1 to [`DAYS`].
+///
+/// ```
+/// # use util::{Day, ParseProblemError};
+/// let day: Day = "12".parse().unwrap();
+/// assert_eq!(day.get(), 12);
+/// assert_eq!(Day::new(0), Err(ParseProblemError::InvalidDay("0".into())));
+/// assert_eq!(Day::all().count(), 12);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Day(u8);
+
+impl Day {
+    pub fn new(day: u8) -> Result<Day, ParseProblemError> {
+        if (1..=DAYS).contains(&day) {
+            Ok(Day(day))
+        } else {
+            Err(ParseProblemError::InvalidDay(day.to_string()))
+        }
+    }
+
+    /// Return the day's number.
+    pub fn get(self) -> u8 {
+        self.0
+    }
+
+    /// Iterate over every day of the event, in order.
+    pub fn all() -> impl Iterator<Item = Day> {
+        (1..=DAYS).map(Day)
+    }
+}
+
+impl FromStr for Day {
+    type Err = ParseProblemError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let day = s
+            .parse()
+            .map_err(|_| ParseProblemError::InvalidDay(s.into()))?;
+        Day::new(day)
+    }
+}
+
+impl Display for Day {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// One of the two parts of each day's problem.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Both parts, in order.
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// Return the part's number, which is also the `level` AoC expects for submissions.
+    pub fn get(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = ParseProblemError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(ParseProblemError::InvalidPart(s.into())),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.get())
+    }
+}
 
 /// Describes a specific Advent of Code problem (e.g. day 1 part 2).
 ///
-/// Problems are expected to be created from strings like `"day1-2"`.
+/// Problems are expected to be created from strings like `"day1-2"`, as they would be from
+/// `$CARGO_BIN_NAME`. Problems sort by year, then day, then part.
 ///
 /// ```
-/// # use util::Problem;
+/// # use util::{Part, Problem, ParseProblemError};
 /// let p1_2: Problem = "day1-2".parse().unwrap();
-/// assert_eq!(p1_2.day, "1");
-/// assert_eq!(p1_2.part, "2");
+/// assert_eq!(p1_2.day.get(), 1);
+/// assert_eq!(p1_2.part, Part::Two);
 ///
 /// let p12_1: Problem = "day12-1".parse().unwrap();
-/// assert_eq!(p12_1.day, "12");
-/// assert_eq!(p12_1.part, "1");
+/// assert_eq!(p12_1.to_string(), "day12-1");
+/// assert!(p1_2 < p12_1);
+///
+/// assert!(matches!("x-1".parse::<Problem>(), Err(ParseProblemError::InvalidFormat(_))));
+/// assert!(matches!("day99-1".parse::<Problem>(), Err(ParseProblemError::InvalidDay(_))));
+/// assert!(matches!("day9-7".parse::<Problem>(), Err(ParseProblemError::InvalidPart(_))));
+/// assert_eq!(Problem::all().count(), 24);
 /// ```
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Problem {
-    /// Which AOC day (year-agnostic) this problem is from.
-    pub day: String,
+    /// Which AoC event this problem is from.
+    pub year: u16,
+
+    /// Which day of the event this problem is from.
+    pub day: Day,
 
     /// Which part of a given day's problem this is.
-    pub part: String,
+    pub part: Part,
+}
+
+impl Problem {
+    /// Create a [`Problem`] for [`YEAR`]'s event.
+    pub fn new(day: Day, part: Part) -> Problem {
+        Problem {
+            year: YEAR,
+            day,
+            part,
+        }
+    }
+
+    /// Iterate over every problem of [`YEAR`]'s event, in order.
+    pub fn all() -> impl Iterator<Item = Problem> {
+        Day::all().flat_map(|day| Part::ALL.map(|part| Problem::new(day, part)))
+    }
 }
 
 impl FromStr for Problem {
     type Err = ParseProblemError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let hyphen_pos = s
-            .bytes()
-            .position(|c| c == b'-')
-            .ok_or(ParseProblemError("cannot find day/part delimiter".into()))?;
-        Ok(Problem {
-            day: s[3..hyphen_pos].into(),
-            part: s[hyphen_pos + 1..].into(),
-        })
+        let (day, part) = s
+            .strip_prefix("day")
+            .and_then(|rest| rest.split_once('-'))
+            .ok_or_else(|| ParseProblemError::InvalidFormat(s.into()))?;
+        Ok(Problem::new(day.parse()?, part.parse()?))
     }
 }
 
@@ -57,8 +197,10 @@ impl Display for Problem {
     }
 }
 
-/// Type alias for solution functions. Solutions should return their results as a [`String`].
-pub type SolveFn = fn(BufReader<File>) -> anyhow::Result<String>;
+/// Type alias for solution functions. Solutions are passed the contents of the input file, which
+/// implements [`BufRead`](std::io::BufRead) for reading it line by line, and should return their
+/// results as a [`String`].
+pub type SolveFn = fn(&[u8]) -> anyhow::Result<String>;
 
 #[derive(Debug)]
 pub struct Solution {
@@ -66,6 +208,13 @@ pub struct Solution {
 
     pub solve_fn: SolveFn,
 
+    /// Named alternative implementations of `solve_fn` (e.g. a slow reference implementation kept
+    /// around while optimizing). See [`compare::compare`].
+    pub alternatives: Vec<(&'static str, SolveFn)>,
+
+    /// Generates random inputs for comparing `solve_fn` against `alternatives`.
+    pub generator: Option<compare::GenerateFn>,
+
     pub working_dir: PathBuf,
 }
 
@@ -86,6 +235,14 @@ impl Solution {
         input_filepath
     }
 
+    /// Return the path to the file where a record of each run of this day's solutions is kept
+    /// (e.g. `day1/history.jsonl`).
+    pub fn history_file(&self) -> PathBuf {
+        let mut history_filepath = self.working_dir.clone();
+        history_filepath.push("history.jsonl");
+        history_filepath
+    }
+
     /// Return the path to a file that may be used to cache correct solutions for this problem.
     ///
     /// By default, the path for day 1 part 1 will be `day1/day1-1.solution`. However, if
@@ -103,42 +260,107 @@ impl Solution {
     }
 }
 
-/// Define a `main` function for solutions. Assumes the solution function ([`SolveFn`]) is named
-/// `solve()`.
+/// Define a `solution()` function which returns the [`Solution`] for a part, registered as a given
+/// [`Problem`]. Assumes the solution function ([`SolveFn`]) is named `solve()`.
+///
+/// Each part of a day is a module of the day's library (e.g. `day1::part_1`), so that the day's
+/// binaries and [`aoc_tests!`] can all use it.
 ///
 /// Example:
-/// ```ignore
-/// # // This doctest fails because it doesn't depend on `tracing_subscriber`
-/// use std::fs::File;
-/// use std::io::{BufRead, BufReader};
+/// ```
+/// use std::io::BufRead;
 ///
-/// fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
-///     for line in reader.lines() {
-///         tracing::info!("{line:?}");
+/// pub fn solve(input: &[u8]) -> anyhow::Result<String> {
+///     let mut sum = 0;
+///     for line in input.lines() {
+///         sum += line?.parse::<u64>()?;
 ///     }
-///     Ok("123".into())
+///     Ok(sum.to_string())
 /// }
 ///
-/// util::main!();
+/// util::solution!();
+///
+/// let solution = solution("day1-1".parse().unwrap());
+/// assert_eq!((solution.solve_fn)(b"1\n2\n").unwrap(), "3");
+/// ```
+///
+/// Alternative implementations of `solve()` and a random input generator
+/// ([`compare::GenerateFn`]) can be registered for `solve --compare`:
+/// ```ignore
+/// util::solution!(alternatives: [naive => solve_naive], generator: generate);
 /// ```
 #[macro_export]
-macro_rules! main {
+macro_rules! solution {
     () => {
+        $crate::solution!(@solution [] None);
+    };
+    (alternatives: [$($name:ident => $alternative:path),* $(,)?] $(,)?) => {
+        $crate::solution!(@solution [$($name => $alternative),*] None);
+    };
+    (alternatives: [$($name:ident => $alternative:path),* $(,)?], generator: $generator:path $(,)?) => {
+        $crate::solution!(@solution [$($name => $alternative),*] Some($generator));
+    };
+    (generator: $generator:path $(,)?) => {
+        $crate::solution!(@solution [] Some($generator));
+    };
+    (@solution [$($name:ident => $alternative:path),*] $generator:expr) => {
+        /// Return this part's [`util::Solution`], registered as `problem`.
+        pub fn solution(problem: $crate::Problem) -> $crate::Solution {
+            $crate::Solution {
+                problem,
+                solve_fn: solve,
+                alternatives: vec![$((stringify!($name), $alternative)),*],
+                generator: $generator,
+                working_dir: std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+            }
+        }
+    };
+}
+
+/// Define a `main` function for a binary which runs a part's solution, given the module where
+/// [`solution!`] defined its `solution()` function. The binary's name is expected to be the
+/// problem (e.g. `day1-2`).
+///
+/// Example:
+/// ```ignore
+/// # // This doctest fails because `$CARGO_BIN_NAME` is only set when building binaries
+/// util::main!(day1::part_2);
+/// ```
+///
+/// A binary which runs both parts of a day back to back can be defined by passing each part's
+/// module. The binary's name is expected to be the day without a part (e.g. `day1`).
+/// ```ignore
+/// # // This doctest fails because `$CARGO_BIN_NAME` is only set when building binaries
+/// util::main!(day1::part_1, day1::part_2);
+/// ```
+#[macro_export]
+macro_rules! main {
+    ($($part:ident)::+) => {
+        $crate::global_allocator!();
+
         pub fn main() -> anyhow::Result<()> {
-            tracing_subscriber::fmt::init();
+            use $crate::cli::{SolutionCli, clap::Parser};
+            let cli = SolutionCli::parse();
+            let _tracing = cli.init_tracing(env!("CARGO_MANIFEST_DIR").as_ref())?;
 
-            use std::path::PathBuf;
-            use std::str::FromStr;
-            use util::{Problem, Solution};
-            let solution = Solution {
-                problem: env!("CARGO_BIN_NAME").parse()?,
-                solve_fn: solve,
-                working_dir: PathBuf::from(env!("CARGO_MANIFEST_DIR")),
-            };
+            let solution = $($part)::+::solution(env!("CARGO_BIN_NAME").parse()?);
+            cli.run(&[solution])?;
+            Ok(())
+        }
+    };
+    ($($part_1:ident)::+, $($part_2:ident)::+) => {
+        $crate::global_allocator!();
 
-            use util::cli::{SolutionCli, clap::Parser};
+        pub fn main() -> anyhow::Result<()> {
+            use $crate::cli::{SolutionCli, clap::Parser};
             let cli = SolutionCli::parse();
-            cli.run(&solution)?;
+            let _tracing = cli.init_tracing(env!("CARGO_MANIFEST_DIR").as_ref())?;
+
+            let solutions = [
+                $($part_1)::+::solution(concat!(env!("CARGO_BIN_NAME"), "-1").parse()?),
+                $($part_2)::+::solution(concat!(env!("CARGO_BIN_NAME"), "-2").parse()?),
+            ];
+            cli.run(&solutions)?;
             Ok(())
         }
     };
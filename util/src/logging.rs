@@ -0,0 +1,194 @@
+use std::fs::File;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::prelude::*;
+
+use crate::timing::{SpanTimingLayer, SpanTimings};
+
+/// Log levels from least to most verbose. [`TracingOptions::verbosity`] moves up or down this list
+/// starting from `INFO`.
+const LEVELS: [LevelFilter; 6] = [
+    LevelFilter::OFF,
+    LevelFilter::ERROR,
+    LevelFilter::WARN,
+    LevelFilter::INFO,
+    LevelFilter::DEBUG,
+    LevelFilter::TRACE,
+];
+
+/// Options controlling how solution binaries set up `tracing`.
+#[derive(Clone, Debug, Default)]
+pub struct TracingOptions {
+    /// How many levels more (positive) or less (negative) verbose than `INFO` to log.
+    pub verbosity: i8,
+
+    /// A filter like `day4=trace,info`, which takes precedence over `verbosity`. If neither is
+    /// set, `$RUST_LOG` is used.
+    pub log: Option<String>,
+
+    /// Write logs to this file instead of the terminal. Warnings and errors are still logged to
+    /// the terminal.
+    pub log_file: Option<PathBuf>,
+
+    /// Print a hierarchical summary of time spent in each span at exit.
+    pub timings: bool,
+
+    /// Write every span to this file as a Chrome trace event JSON file at exit.
+    pub trace_json: Option<PathBuf>,
+}
+
+/// Keeps span timings around until the program exits. When dropped, prints the timing summary
+/// and writes the Chrome trace requested by [`TracingOptions`].
+pub struct TracingGuard {
+    options: TracingOptions,
+    timings: Option<SpanTimings>,
+}
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        let Some(timings) = &self.timings else {
+            return;
+        };
+        if self.options.timings
+            && let Err(e) = timings.print_summary(&mut std::io::stderr().lock())
+        {
+            tracing::warn!("Failed to print span timings: {e}");
+        }
+        if let Some(trace_json) = &self.options.trace_json {
+            match timings.write_chrome_trace(trace_json) {
+                Ok(()) => tracing::info!("Wrote trace to `{}`", trace_json.display()),
+                Err(e) => {
+                    tracing::warn!("Failed to write trace to `{}`: {e}", trace_json.display())
+                }
+            }
+        }
+    }
+}
+
+/// Filter log output according to `$RUST_LOG` (e.g. `RUST_LOG=day4=trace,info`), defaulting to
+/// `info`.
+fn env_targets() -> Targets {
+    match std::env::var("RUST_LOG") {
+        Ok(var) => Targets::from_str(&var).unwrap_or_else(|e| {
+            eprintln!("Ignoring `RUST_LOG={var:?}`: {e}");
+            Targets::new().with_default(LevelFilter::INFO)
+        }),
+        Err(_) => Targets::new().with_default(LevelFilter::INFO),
+    }
+}
+
+impl TracingOptions {
+    /// The log filter selected by these options.
+    fn targets(&self) -> anyhow::Result<Targets> {
+        if let Some(log) = &self.log {
+            return Targets::from_str(log)
+                .map_err(|e| anyhow::anyhow!("invalid log filter `{log}`: {e}"));
+        }
+        if self.verbosity == 0 {
+            return Ok(env_targets());
+        }
+        let info = LEVELS
+            .iter()
+            .position(|level| *level == LevelFilter::INFO)
+            .unwrap_or_default();
+        let level = info
+            .saturating_add_signed(self.verbosity.into())
+            .min(LEVELS.len() - 1);
+        Ok(Targets::new().with_default(LEVELS[level]))
+    }
+}
+
+/// Return the current UTC time formatted for use in a filename (e.g. `2025-12-05T14-03-22Z`).
+pub fn filename_timestamp() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let (days, secs_of_day) = (secs / 86400, secs % 86400);
+
+    // Convert days since the Unix epoch to a civil date. See
+    // <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}-{:02}-{:02}Z",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Return the default log file for a day (e.g. `day1/logs/2025-12-05T14-03-22Z.log`).
+pub fn default_log_file(working_dir: &Path) -> PathBuf {
+    working_dir
+        .join("logs")
+        .join(format!("{}.log", filename_timestamp()))
+}
+
+/// Install a global `tracing` subscriber which logs to stderr (or a log file) and, if requested,
+/// records span timings with a [`SpanTimingLayer`].
+///
+/// Logs never go to stdout, so a solution's answer can be piped. Colors are only used when stderr
+/// is a terminal. Span timings are recorded for every span regardless of the log filter. Hold on to
+/// the returned [`TracingGuard`] until the program exits.
+pub fn init(options: &TracingOptions) -> anyhow::Result<TracingGuard> {
+    let targets = options.targets()?;
+
+    let (terminal_targets, file_layer) = match &options.log_file {
+        Some(log_file) => {
+            if let Some(log_dir) = log_file.parent() {
+                std::fs::create_dir_all(log_dir)?;
+            }
+            let file_layer = tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(Mutex::new(File::create(log_file)?))
+                .with_filter(targets);
+            (
+                Targets::new().with_default(LevelFilter::WARN),
+                Some(file_layer),
+            )
+        }
+        None => (targets, None),
+    };
+    let terminal_layer = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .with_filter(terminal_targets);
+
+    let timing_layer = (options.timings || options.trace_json.is_some())
+        .then(|| SpanTimingLayer::new(options.trace_json.is_some()));
+    let (timing_layer, timings) = timing_layer.unzip();
+
+    tracing_subscriber::registry()
+        .with(terminal_layer)
+        .with(file_layer)
+        .with(timing_layer)
+        .init();
+
+    if let Some(log_file) = &options.log_file {
+        eprintln!("Logging to `{}`", log_file.display());
+    }
+
+    Ok(TracingGuard {
+        options: options.clone(),
+        timings,
+    })
+}
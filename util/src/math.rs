@@ -0,0 +1,392 @@
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Neg, Range, Rem, Sub};
+
+/// The primitive integer types, so number-theory helpers can be written once.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    fn from_u8(value: u8) -> Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    fn wrapping_sub(self, rhs: Self) -> Self;
+
+    fn wrapping_mul(self, rhs: Self) -> Self;
+
+    /// The unsigned type of the same width, which can hold the distance between any two values.
+    type Unsigned: Integer;
+
+    /// The absolute value, wrapping for a signed type's `MIN`.
+    fn wrapping_abs(self) -> Self;
+
+    /// The distance between `self` and `other`, which can't overflow.
+    fn abs_diff(self, other: Self) -> Self::Unsigned;
+
+    /// `self + offset`, wrapping around at the type's bounds.
+    fn wrapping_add_unsigned(self, offset: Self::Unsigned) -> Self;
+
+    /// `value` as this type, or `None` if it doesn't fit.
+    fn try_from_unsigned(value: Self::Unsigned) -> Option<Self>;
+
+    /// The floor of the square root. Panics if `self` is negative.
+    fn isqrt(self) -> Self;
+
+    /// `(self * rhs) % modulus` without overflowing. Expects `0 <= self, rhs < modulus`.
+    fn mul_mod(self, rhs: Self, modulus: Self) -> Self;
+}
+
+/// Signed primitive integer types.
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+/// `(a + b) % modulus` without overflowing. Expects `0 <= a, b < modulus`.
+fn add_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+/// `(a - b) % modulus`, wrapped to be non-negative. Expects `0 <= a, b < modulus`.
+fn sub_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    if a >= b { a - b } else { modulus - (b - a) }
+}
+
+macro_rules! impl_integer {
+    // 128-bit types, which multiply by doubling and adding instead.
+    (@double $($t:ty: $unsigned:ty),*) => {
+        $(
+            impl Integer for $t {
+                impl_integer!(@common $t: $unsigned);
+
+                fn mul_mod(self, rhs: Self, modulus: Self) -> Self {
+                    let (mut a, mut b, mut product) = (self % modulus, rhs, 0);
+                    while b > 0 {
+                        if b & 1 == 1 {
+                            product = add_mod(product, a, modulus);
+                        }
+                        a = add_mod(a, a, modulus);
+                        b >>= 1;
+                    }
+                    product
+                }
+            }
+        )*
+    };
+    (@common $t:ty: $unsigned:ty) => {
+        type Unsigned = $unsigned;
+
+        const ZERO: Self = 0;
+        const ONE: Self = 1;
+        const MIN: Self = <$t>::MIN;
+        const MAX: Self = <$t>::MAX;
+
+        fn from_u8(value: u8) -> Self {
+            value as $t
+        }
+
+        fn checked_add(self, rhs: Self) -> Option<Self> {
+            <$t>::checked_add(self, rhs)
+        }
+
+        fn checked_mul(self, rhs: Self) -> Option<Self> {
+            <$t>::checked_mul(self, rhs)
+        }
+
+        fn wrapping_sub(self, rhs: Self) -> Self {
+            <$t>::wrapping_sub(self, rhs)
+        }
+
+        fn wrapping_mul(self, rhs: Self) -> Self {
+            <$t>::wrapping_mul(self, rhs)
+        }
+
+        #[allow(unused_comparisons)]
+        fn wrapping_abs(self) -> Self {
+            if self < 0 { (0 as $t).wrapping_sub(self) } else { self }
+        }
+
+        fn abs_diff(self, other: Self) -> $unsigned {
+            <$t>::abs_diff(self, other)
+        }
+
+        fn wrapping_add_unsigned(self, offset: $unsigned) -> Self {
+            (self as $unsigned).wrapping_add(offset) as $t
+        }
+
+        fn try_from_unsigned(value: $unsigned) -> Option<Self> {
+            <$t>::try_from(value).ok()
+        }
+
+        fn isqrt(self) -> Self {
+            <$t>::isqrt(self)
+        }
+    };
+    // Types whose products fit in a wider type.
+    ($($t:ty: $unsigned:ty => $wide:ty),* $(,)?) => {
+        $(
+            impl Integer for $t {
+                impl_integer!(@common $t: $unsigned);
+
+                fn mul_mod(self, rhs: Self, modulus: Self) -> Self {
+                    ((self as $wide * rhs as $wide) % modulus as $wide) as $t
+                }
+            }
+        )*
+    };
+}
+
+// Each type is followed by its unsigned counterpart, then the type its products are computed in.
+impl_integer!(
+    u8: u8 => u16,
+    u16: u16 => u32,
+    u32: u32 => u64,
+    u64: u64 => u128,
+    usize: usize => u128,
+    i8: u8 => i16,
+    i16: u16 => i32,
+    i32: u32 => i64,
+    i64: u64 => i128,
+    isize: usize => i128,
+);
+impl_integer!(@double u128: u128, i128: u128);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+/// The greatest common divisor of the magnitudes of `a` and `b`, which can't overflow.
+fn unsigned_gcd<T: Integer>(a: T, b: T) -> T::Unsigned {
+    let (mut a, mut b) = (a.abs_diff(T::ZERO), b.abs_diff(T::ZERO));
+    while b != T::Unsigned::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The greatest common divisor of `a` and `b`, which is never negative, or `None` if it doesn't
+/// fit in `T` (e.g. `gcd(i32::MIN, 0)`). `gcd(0, 0)` is 0.
+///
+/// ```
+/// # use util::math::gcd;
+/// assert_eq!(gcd(12u32, 18), Some(6));
+/// assert_eq!(gcd(-12i64, 18), Some(6));
+/// assert_eq!(gcd(0u8, 7), Some(7));
+/// assert_eq!(gcd(i32::MIN, -1), Some(1));
+/// assert_eq!(gcd(i32::MIN, 0), None);
+/// ```
+pub fn gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    T::try_from_unsigned(unsigned_gcd(a, b))
+}
+
+/// The least common multiple of `a` and `b`, which is never negative, or `None` if it overflows.
+/// `lcm(0, n)` is 0.
+///
+/// ```
+/// # use util::math::lcm;
+/// assert_eq!(lcm(4u64, 6), Some(12));
+/// assert_eq!(lcm(200u8, 3), None);
+/// assert_eq!(lcm(i32::MIN, -1), None);
+/// ```
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    let magnitude = (a.abs_diff(T::ZERO) / unsigned_gcd(a, b)).checked_mul(b.abs_diff(T::ZERO))?;
+    T::try_from_unsigned(magnitude)
+}
+
+/// The extended Euclidean algorithm: returns `(g, x, y)` where `g` is [`gcd`]`(a, b)` and
+/// `a * x + b * y == g`, or `None` if `g` doesn't fit in `T`.
+///
+/// ```
+/// # use util::math::extended_gcd;
+/// let (g, x, y) = extended_gcd(240i32, 46).unwrap();
+/// assert_eq!(g, 2);
+/// assert_eq!(240 * x + 46 * y, 2);
+/// assert_eq!(extended_gcd(i32::MIN, 0), None);
+/// ```
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> Option<(T, T, T)> {
+    // The remainders are the inputs' magnitudes, so they can't overflow. The coefficients after
+    // the last step can be just out of range, but the ones returned always fit, so wrapping
+    // arithmetic gets them exactly.
+    let (mut old_r, mut r) = (a.abs_diff(T::ZERO), b.abs_diff(T::ZERO));
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+    while r != T::Unsigned::ZERO {
+        let quotient = T::ZERO.wrapping_add_unsigned(old_r / r);
+        (old_r, r) = (r, old_r % r);
+        (old_x, x) = (x, old_x.wrapping_sub(quotient.wrapping_mul(x)));
+        (old_y, y) = (y, old_y.wrapping_sub(quotient.wrapping_mul(y)));
+    }
+    let g = T::try_from_unsigned(old_r)?;
+    // `|a| * old_x + |b| * old_y == g`, so a negative input flips the sign of its coefficient.
+    let x = if a < T::ZERO { -old_x } else { old_x };
+    let y = if b < T::ZERO { -old_y } else { old_y };
+    Some((g, x, y))
+}
+
+/// Wrap `value` into `0..modulus`, like `rem_euclid`. Panics if `modulus` isn't positive.
+fn reduce<T: Integer>(value: T, modulus: T) -> T {
+    assert!(modulus > T::ZERO, "modulus must be positive");
+    let remainder = value % modulus;
+    if remainder < T::ZERO {
+        remainder + modulus
+    } else {
+        remainder
+    }
+}
+
+/// The inverse of `a` modulo `modulus` (the `x` in `0..modulus` where `a * x % modulus == 1`),
+/// or `None` if `a` and `modulus` aren't coprime. Panics if `modulus` isn't positive.
+///
+/// ```
+/// # use util::math::mod_inverse;
+/// assert_eq!(mod_inverse(3u32, 11), Some(4));
+/// assert_eq!(mod_inverse(-3i32, 11), Some(7));
+/// assert_eq!(mod_inverse(6u32, 9), None);
+/// ```
+pub fn mod_inverse<T: Integer>(a: T, modulus: T) -> Option<T> {
+    // The extended Euclidean algorithm, tracking only `a`'s coefficient and keeping it in
+    // `0..modulus` so it works for unsigned types too.
+    let (mut old_r, mut r) = (reduce(a, modulus), modulus);
+    let (mut old_x, mut x) = (T::ONE % modulus, T::ZERO);
+    while r != T::ZERO {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        let product = reduce(quotient, modulus).mul_mod(x, modulus);
+        (old_x, x) = (x, sub_mod(old_x, product, modulus));
+    }
+    (old_r == T::ONE).then_some(old_x)
+}
+
+/// `base` raised to `exp`, modulo `modulus`, in `0..modulus`. Panics if `modulus` isn't positive.
+///
+/// ```
+/// # use util::math::mod_pow;
+/// assert_eq!(mod_pow(4u32, 13, 497), 445);
+/// assert_eq!(mod_pow(-2i64, 3, 5), 2);
+/// assert_eq!(mod_pow(u128::MAX - 1, 2, u128::MAX), 1);
+/// ```
+pub fn mod_pow<T: Integer>(base: T, mut exp: u64, modulus: T) -> T {
+    let mut base = reduce(base, modulus);
+    let mut result = T::ONE % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.mul_mod(base, modulus);
+        }
+        base = base.mul_mod(base, modulus);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Solve a system of congruences `x ≡ residue (mod modulus)` with the Chinese Remainder Theorem.
+/// Moduli don't need to be coprime.
+///
+/// Returns `(x, lcm)` where `x` is the smallest non-negative solution and every solution is
+/// `x + k * lcm`, or `None` if the congruences conflict or `lcm` overflows. Panics if any modulus
+/// isn't positive.
+///
+/// ```
+/// # use util::math::crt;
+/// assert_eq!(crt(&[(2u64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt(&[(1i32, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(crt(&[(0u32, 4), (1, 6)]), None);
+/// ```
+pub fn crt<T: Integer>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut x = T::ZERO;
+    let mut lcm_so_far = T::ONE;
+    for &(residue, modulus) in congruences {
+        let residue = reduce(residue, modulus);
+        // Both are positive, so this is never `None`.
+        let g = gcd(lcm_so_far, modulus)?;
+        // Find `k` such that `x + lcm_so_far * k ≡ residue (mod modulus)`.
+        let difference = sub_mod(residue, x % modulus, modulus);
+        if difference % g != T::ZERO {
+            return None;
+        }
+        let reduced_modulus = modulus / g;
+        let inverse = mod_inverse(lcm_so_far / g, reduced_modulus)?;
+        let k = (difference / g % reduced_modulus).mul_mod(inverse, reduced_modulus);
+
+        let next_lcm = (lcm_so_far / g).checked_mul(modulus)?;
+        // `x < lcm_so_far` and `lcm_so_far * k <= next_lcm - lcm_so_far`, so this can't overflow.
+        x = x + lcm_so_far * k;
+        lcm_so_far = next_lcm;
+    }
+    Some((x, lcm_so_far))
+}
+
+/// Wrap `value` into `range`, as if the range repeated forever in both directions (e.g. a dial
+/// numbered `0..100`). Panics if `range` is empty.
+///
+/// ```
+/// # use util::math::wrap;
+/// assert_eq!(wrap(105, 0..100), 5);
+/// assert_eq!(wrap(-1, 0..100), 99);
+/// assert_eq!(wrap(0u32, 1..13), 12);
+/// ```
+pub fn wrap<T: Integer>(value: T, range: Range<T>) -> T {
+    assert!(range.start < range.end, "cannot wrap into an empty range");
+    // Distances are unsigned so they can't overflow, even for ranges like `i8::MIN..i8::MAX`.
+    let len = range.end.abs_diff(range.start);
+    let offset = if value >= range.start {
+        value.abs_diff(range.start) % len
+    } else {
+        len - T::Unsigned::ONE - (range.start.abs_diff(value) - T::Unsigned::ONE) % len
+    };
+    range.start.wrapping_add_unsigned(offset)
+}
+
+/// The floor of the square root of `n`. Panics if `n` is negative.
+///
+/// ```
+/// # use util::math::isqrt;
+/// assert_eq!(isqrt(24u64), 4);
+/// assert_eq!(isqrt(25i32), 5);
+/// ```
+pub fn isqrt<T: Integer>(n: T) -> T {
+    n.isqrt()
+}
+
+/// How many decimal digits `n` has, ignoring its sign. 0 has one digit.
+///
+/// ```
+/// # use util::math::digit_count;
+/// assert_eq!(digit_count(0u8), 1);
+/// assert_eq!(digit_count(1234512345u64), 10);
+/// assert_eq!(digit_count(i64::MIN), 19);
+/// ```
+pub fn digit_count<T: Integer>(n: T) -> u32 {
+    crate::digits::count(n, 10)
+}
+
+/// `10^exp`, or `None` if it doesn't fit in `T`.
+///
+/// ```
+/// # use util::math::checked_pow10;
+/// assert_eq!(checked_pow10::<u16>(4), Some(10000));
+/// assert_eq!(checked_pow10::<u16>(5), None);
+/// ```
+pub fn checked_pow10<T: Integer>(exp: u32) -> Option<T> {
+    (0..exp).try_fold(T::ONE, |power, _| power.checked_mul(T::from_u8(10)))
+}
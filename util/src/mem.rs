@@ -0,0 +1,173 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{self, Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+/// Bytes currently allocated.
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+/// The most bytes allocated at once since the last [`measure`] started.
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Number of allocations since the last [`measure`] started.
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Total bytes allocated since the last [`measure`] started.
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// Number of allocations since the program started. Never reset, so a non-zero value means
+/// [`CountingAllocator`] is installed.
+static LIFETIME_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator which wraps [`System`] and counts allocations.
+///
+/// [`main!`](crate::main!) installs it in every solution binary when `util`'s `mem` feature is
+/// enabled:
+/// ```text
+/// $ cargo run --bin day4-1 --features util/mem -- --mem
+/// ```
+///
+/// Other binaries can install it directly:
+/// ```ignore
+/// #[global_allocator]
+/// static GLOBAL: util::mem::CountingAllocator = util::mem::CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+/// Install [`CountingAllocator`] as the global allocator if `util`'s `mem` feature is enabled.
+/// Used by [`main!`](crate::main!), since a library can't decide the global allocator for every
+/// binary which links it.
+#[cfg(feature = "mem")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! global_allocator {
+    () => {
+        #[global_allocator]
+        static GLOBAL: $crate::mem::CountingAllocator = $crate::mem::CountingAllocator;
+    };
+}
+
+/// Install [`CountingAllocator`] as the global allocator if `util`'s `mem` feature is enabled.
+#[cfg(not(feature = "mem"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! global_allocator {
+    () => {};
+}
+
+#[inline]
+fn record_alloc(size: usize) {
+    let current = CURRENT.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(current, Relaxed);
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    ALLOCATED.fetch_add(size, Relaxed);
+    LIFETIME_ALLOCATIONS.fetch_add(1, Relaxed);
+}
+
+#[inline]
+fn record_dealloc(size: usize) {
+    CURRENT.fetch_sub(size, Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            // A reallocation counts as freeing the old block and allocating a new one.
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Whether [`CountingAllocator`] is the global allocator.
+pub fn installed() -> bool {
+    LIFETIME_ALLOCATIONS.load(Relaxed) > 0
+}
+
+/// Allocation statistics collected by [`CountingAllocator`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// The most heap memory in use at once, in bytes, relative to when measurement started.
+    pub peak: usize,
+
+    /// Number of allocations (including reallocations).
+    pub allocations: usize,
+
+    /// Total bytes allocated.
+    pub allocated: usize,
+}
+
+/// Format a byte count with a binary unit suffix (e.g. `12.3 KiB`).
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "peak heap {}, {} allocations, {} allocated",
+            format_bytes(self.peak),
+            self.allocations,
+            format_bytes(self.allocated)
+        )
+    }
+}
+
+/// Run `f` and collect allocation statistics for it. Returns `None` for the statistics if
+/// [`CountingAllocator`] isn't installed.
+///
+/// Statistics are process-wide, so allocations made by other threads while `f` runs are counted
+/// too.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !installed() {
+        return (f(), None);
+    }
+
+    let baseline = CURRENT.load(Relaxed);
+    PEAK.store(baseline, Relaxed);
+    ALLOCATIONS.store(0, Relaxed);
+    ALLOCATED.store(0, Relaxed);
+
+    let result = f();
+
+    let stats = AllocStats {
+        peak: PEAK.load(Relaxed).saturating_sub(baseline),
+        allocations: ALLOCATIONS.load(Relaxed),
+        allocated: ALLOCATED.load(Relaxed),
+    };
+    (result, Some(stats))
+}
@@ -1,33 +1,501 @@
+use std::collections::BTreeMap;
+use std::fmt;
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 
-#[derive(thiserror::Error, Debug)]
-#[error("invalid range: {0}")]
-pub struct RangeError(String);
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum RangeError {
+    #[error("invalid range `{range}`: missing `{separator}` separator")]
+    MissingSeparator {
+        range: String,
+        separator: &'static str,
+    },
+
+    #[error("invalid range `{range}`: cannot parse start `{start}`")]
+    InvalidStart { range: String, start: String },
+
+    #[error("invalid range `{range}`: cannot parse end `{end}`")]
+    InvalidEnd { range: String, end: String },
+
+    #[error("invalid range `{0}`: exclusive end leaves it empty")]
+    Empty(String),
+
+    /// An exclusive end (see [`RangeSeparator::Dots`]) was given to [`ParseRange`], which can't
+    /// find the value before it.
+    #[error("invalid range `{0}`: excluding the end requires `ParseOpenRange`")]
+    ExclusiveEnd(String),
+}
+
+/// What separates the start and end of a range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangeSeparator {
+    /// `3-5`. A `-` at the start of either number is treated as a sign, so `-5--1` is `-5..=-1`.
+    Hyphen,
+
+    /// `3..5`, which excludes its end like Rust's `Range`.
+    Dots,
+
+    /// `3..=5`.
+    DotsEq,
+
+    /// `3 to 5`.
+    To,
+
+    /// `3,5`.
+    Comma,
+}
+
+impl RangeSeparator {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RangeSeparator::Hyphen => "-",
+            RangeSeparator::Dots => "..",
+            RangeSeparator::DotsEq => "..=",
+            RangeSeparator::To => "to",
+            RangeSeparator::Comma => ",",
+        }
+    }
+
+    /// Split `s` into its start and end around this separator.
+    fn split(self, s: &str) -> Option<(&str, &str)> {
+        match self {
+            // The first `-` after the first character, so a leading minus sign is skipped.
+            RangeSeparator::Hyphen => {
+                let (first_len, rest) = s
+                    .chars()
+                    .next()
+                    .map(|c| (c.len_utf8(), &s[c.len_utf8()..]))?;
+                let hyphen_pos = first_len + rest.find('-')?;
+                Some((&s[..hyphen_pos], &s[hyphen_pos + 1..]))
+            }
+            // Don't mistake `..=` for `..`.
+            RangeSeparator::Dots => s.split_once("..").filter(|(_, end)| !end.starts_with('=')),
+            separator => s.split_once(separator.as_str()),
+        }
+    }
+}
 
 /// Trait for parsing `0-100`-type ranges that often appear in AOC inputs.
+///
+/// Both sides of the range must be given, and can be anything that implements [`FromStr`] (e.g.
+/// integers, floats or `char`s). To leave a side out, or to exclude the end with
+/// [`RangeSeparator::Dots`], the type also needs to implement [`Step`]; see [`ParseOpenRange`].
+///
+/// ```
+/// # use std::ops::RangeInclusive;
+/// # use util::range::{ParseRange, RangeError, RangeSeparator};
+/// assert_eq!(RangeInclusive::<u64>::parse_range("11-22"), Ok(11..=22));
+/// assert_eq!(RangeInclusive::<i32>::parse_range(" -5--1 "), Ok(-5..=-1));
+/// assert_eq!(<(i32, i32)>::parse_range("-5-10"), Ok((-5, 10)));
+/// assert_eq!(<(f64, f64)>::parse_range("0.5-1.5"), Ok((0.5, 1.5)));
+///
+/// let parse = |s, separator| RangeInclusive::<char>::parse_range_with(s, separator);
+/// assert_eq!(parse("a..=z", RangeSeparator::DotsEq), Ok('a'..='z'));
+/// assert_eq!(parse("a to z", RangeSeparator::To), Ok('a'..='z'));
+/// assert_eq!(parse("a,z", RangeSeparator::Comma), Ok('a'..='z'));
+///
+/// assert_eq!(
+///     RangeInclusive::<u64>::parse_range("x-5"),
+///     Err(RangeError::InvalidStart { range: "x-5".into(), start: "x".into() })
+/// );
+/// assert_eq!(
+///     RangeInclusive::<u64>::parse_range("3-y"),
+///     Err(RangeError::InvalidEnd { range: "3-y".into(), end: "y".into() })
+/// );
+/// ```
 pub trait ParseRange: std::marker::Sized {
-    fn parse_range(s: &str) -> Result<Self, RangeError>;
+    /// Parse a range like `3-5` (see [`RangeSeparator::Hyphen`]).
+    fn parse_range(s: &str) -> Result<Self, RangeError> {
+        Self::parse_range_with(s, RangeSeparator::Hyphen)
+    }
+
+    /// Parse a range whose start and end are separated by `separator`.
+    fn parse_range_with(s: &str, separator: RangeSeparator) -> Result<Self, RangeError>;
+}
+
+/// Trait for parsing ranges like [`ParseRange`], but which may leave out either side to leave the
+/// range unbounded on that side, so `5-` runs from 5 to the type's maximum. With
+/// [`RangeSeparator::Dots`], the end is excluded.
+///
+/// With [`RangeSeparator::Hyphen`], a leading `-` is read as a minus sign, so `-5` is a
+/// [`RangeError::MissingSeparator`] rather than a range ending at 5. Use another separator like
+/// [`RangeSeparator::DotsEq`] (`..=5`) to leave out the start.
+///
+/// ```
+/// # use std::ops::RangeInclusive;
+/// # use util::range::{ParseOpenRange, RangeSeparator};
+/// assert_eq!(<(u8, u8)>::parse_open_range("250-"), Ok((250, 255)));
+/// assert_eq!(RangeInclusive::<u64>::parse_open_range("11-22"), Ok(11..=22));
+///
+/// let parse = |s, separator| RangeInclusive::<i64>::parse_open_range_with(s, separator);
+/// assert_eq!(parse("3..5", RangeSeparator::Dots), Ok(3..=4));
+/// assert_eq!(parse("..=5", RangeSeparator::DotsEq), Ok(i64::MIN..=5));
+/// assert_eq!(parse("-3 to", RangeSeparator::To), Ok(-3..=i64::MAX));
+/// ```
+pub trait ParseOpenRange: std::marker::Sized {
+    /// Parse a range like `3-5` or `3-` (see [`RangeSeparator::Hyphen`]).
+    fn parse_open_range(s: &str) -> Result<Self, RangeError> {
+        Self::parse_open_range_with(s, RangeSeparator::Hyphen)
+    }
+
+    /// Parse a range whose start and end are separated by `separator`, either of which may be
+    /// left out.
+    fn parse_open_range_with(s: &str, separator: RangeSeparator) -> Result<Self, RangeError>;
+}
+
+/// Split a range around `separator`. Returns the trimmed range, start and end.
+fn split_range(s: &str, separator: RangeSeparator) -> Result<(&str, &str, &str), RangeError> {
+    let range = s.trim();
+    let (start, end) = separator
+        .split(range)
+        .ok_or_else(|| RangeError::MissingSeparator {
+            range: range.into(),
+            separator: separator.as_str(),
+        })?;
+    Ok((range, start.trim(), end.trim()))
+}
+
+fn parse_start<T: FromStr>(range: &str, start: &str) -> Result<T, RangeError> {
+    start.parse().map_err(|_| RangeError::InvalidStart {
+        range: range.into(),
+        start: start.into(),
+    })
+}
+
+fn parse_end<T: FromStr>(range: &str, end: &str) -> Result<T, RangeError> {
+    end.parse().map_err(|_| RangeError::InvalidEnd {
+        range: range.into(),
+        end: end.into(),
+    })
+}
+
+/// Parse the inclusive start and end of a range. Shared by every [`ParseRange`] impl.
+fn parse_bounds<T: FromStr>(s: &str, separator: RangeSeparator) -> Result<(T, T), RangeError> {
+    let (range, start, end) = split_range(s, separator)?;
+    if separator == RangeSeparator::Dots {
+        return Err(RangeError::ExclusiveEnd(range.into()));
+    }
+    Ok((parse_start(range, start)?, parse_end(range, end)?))
+}
+
+/// Parse the inclusive start and end of a range whose sides may be left out. Shared by every
+/// [`ParseOpenRange`] impl.
+fn parse_open_bounds<T: FromStr + Step>(
+    s: &str,
+    separator: RangeSeparator,
+) -> Result<(T, T), RangeError> {
+    let (range, start, end) = split_range(s, separator)?;
+    let start = match start {
+        "" => T::MIN,
+        start => parse_start(range, start)?,
+    };
+    let end = match end {
+        "" => T::MAX,
+        end => {
+            let end: T = parse_end(range, end)?;
+            if separator == RangeSeparator::Dots {
+                end.predecessor()
+                    .ok_or_else(|| RangeError::Empty(range.into()))?
+            } else {
+                end
+            }
+        }
+    };
+    Ok((start, end))
 }
 
 impl<T: FromStr> ParseRange for RangeInclusive<T> {
-    fn parse_range(s: &str) -> Result<Self, RangeError> {
-        let (start, end) = s.split_once('-').ok_or(RangeError(s.into()))?;
-        let (start, end) = (
-            start.parse::<T>().map_err(|_| RangeError(s.into()))?,
-            end.trim().parse::<T>().map_err(|_| RangeError(s.into()))?,
-        );
+    fn parse_range_with(s: &str, separator: RangeSeparator) -> Result<Self, RangeError> {
+        let (start, end) = parse_bounds(s, separator)?;
         Ok(start..=end)
     }
 }
 
 impl<T: FromStr> ParseRange for (T, T) {
-    fn parse_range(s: &str) -> Result<Self, RangeError> {
-        let (start, end) = s.split_once('-').ok_or(RangeError(s.into()))?;
-        let (start, end) = (
-            start.parse::<T>().map_err(|_| RangeError(s.into()))?,
-            end.trim().parse::<T>().map_err(|_| RangeError(s.into()))?,
-        );
-        Ok((start, end))
+    fn parse_range_with(s: &str, separator: RangeSeparator) -> Result<Self, RangeError> {
+        parse_bounds(s, separator)
+    }
+}
+
+impl<T: FromStr + Step> ParseOpenRange for RangeInclusive<T> {
+    fn parse_open_range_with(s: &str, separator: RangeSeparator) -> Result<Self, RangeError> {
+        let (start, end) = parse_open_bounds(s, separator)?;
+        Ok(start..=end)
+    }
+}
+
+impl<T: FromStr + Step> ParseOpenRange for (T, T) {
+    fn parse_open_range_with(s: &str, separator: RangeSeparator) -> Result<Self, RangeError> {
+        parse_open_bounds(s, separator)
+    }
+}
+
+/// Integer-like types which [`IntervalSet`] can hold: totally ordered, bounded, and able to step to
+/// the next or previous value.
+pub trait Step: Copy + Ord {
+    const MIN: Self;
+    const MAX: Self;
+
+    /// The next value, or `None` if `self` is [`Step::MAX`].
+    fn successor(self) -> Option<Self>;
+
+    /// The previous value, or `None` if `self` is [`Step::MIN`].
+    fn predecessor(self) -> Option<Self>;
+
+    /// How many values are in `start..=end`, saturating at `u128::MAX`. Expects `start <= end`.
+    fn count(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_step {
+    ($($t:ty),*) => {
+        $(
+            impl Step for $t {
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn predecessor(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count(start: Self, end: Self) -> u128 {
+                    (end.abs_diff(start) as u128).saturating_add(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_step!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/// A set of values stored as disjoint inclusive ranges. Overlapping and adjacent ranges are merged
+/// when inserted, so `1..=3` and `4..=6` are stored as `1..=6`.
+///
+/// Inserting, removing and checking whether a value is contained take O(log n) time (amortized,
+/// for insertion and removal) where n is the number of disjoint ranges.
+///
+/// ```
+/// # use util::range::IntervalSet;
+/// let mut fresh: IntervalSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+/// assert!(fresh.contains(5));
+/// assert!(!fresh.contains(8));
+/// assert!(fresh.contains(17));
+/// assert_eq!(fresh.len(), 14);
+///
+/// fresh.insert(6..=9);
+/// assert_eq!(fresh.ranges().collect::<Vec<_>>(), vec![3..=20]);
+///
+/// fresh.remove(10..=11);
+/// assert_eq!(fresh.ranges().collect::<Vec<_>>(), vec![3..=9, 12..=20]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// Maps the start of each range to its end.
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T: Step> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Step> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet {
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    /// Create a set containing every value of `T`.
+    pub fn full() -> IntervalSet<T> {
+        IntervalSet {
+            ranges: BTreeMap::from([(T::MIN, T::MAX)]),
+        }
+    }
+
+    /// Whether the set has no values.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many values are in the set, saturating at `u128::MAX`. Doesn't enumerate them.
+    pub fn len(&self) -> u128 {
+        self.ranges.iter().fold(0u128, |len, (start, end)| {
+            len.saturating_add(T::count(*start, *end))
+        })
+    }
+
+    /// How many disjoint ranges the set is made of.
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Whether `value` is in the set.
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, end)| *end >= value)
+    }
+
+    /// Add every value in `range` to the set, merging it with any ranges it overlaps or touches.
+    /// Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // Ranges starting after `end + 1` can't overlap or touch the new range. Walk backwards
+        // from the last range that might, absorbing ranges until one ends before `start - 1`.
+        let search_end = end.successor().unwrap_or(end);
+        while let Some((&other_start, &other_end)) = self.ranges.range(..=search_end).next_back() {
+            if other_end.successor().is_some_and(|next| next < start) {
+                break;
+            }
+            self.ranges.remove(&other_start);
+            start = start.min(other_start);
+            end = end.max(other_end);
+        }
+        self.ranges.insert(start, end);
+    }
+
+    /// Remove every value in `range` from the set, splitting ranges it partially overlaps. Empty
+    /// ranges are ignored.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        let mut remainders = vec![];
+        while let Some((&other_start, &other_end)) = self.ranges.range(..=end).next_back() {
+            if other_end < start {
+                break;
+            }
+            self.ranges.remove(&other_start);
+            if other_end > end {
+                // `end < other_end <= T::MAX`, so `end` has a successor.
+                remainders.extend(end.successor().map(|after| (after, other_end)));
+            }
+            if other_start < start {
+                remainders.extend(start.predecessor().map(|before| (other_start, before)));
+            }
+        }
+        self.ranges.extend(remainders);
+    }
+
+    /// Iterate over the set's disjoint ranges from lowest to highest. Consecutive ranges never
+    /// touch.
+    pub fn ranges(&self) -> impl DoubleEndedIterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|(start, end)| *start..=*end)
+    }
+
+    /// Iterate over every value in the set from lowest to highest.
+    pub fn values(&self) -> impl Iterator<Item = T> + '_ {
+        self.ranges.iter().flat_map(|(start, end)| {
+            std::iter::successors(Some(*start), move |value| {
+                value.successor().filter(|next| next <= end)
+            })
+        })
+    }
+
+    /// Return the set of values in `self`, `other`, or both.
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut union = self.clone();
+        union.extend(other.ranges());
+        union
+    }
+
+    /// Return the set of values in both `self` and `other`.
+    ///
+    /// ```
+    /// # use util::range::IntervalSet;
+    /// let a: IntervalSet<i32> = [-10..=0, 5..=8].into_iter().collect();
+    /// let b: IntervalSet<i32> = [-3..=6].into_iter().collect();
+    /// assert_eq!(a.intersection(&b).ranges().collect::<Vec<_>>(), vec![-3..=0, 5..=6]);
+    /// ```
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intersection = IntervalSet::new();
+        let mut ours = self.ranges.iter().peekable();
+        let mut theirs = other.ranges.iter().peekable();
+        while let (Some(&(&a_start, &a_end)), Some(&(&b_start, &b_end))) =
+            (ours.peek(), theirs.peek())
+        {
+            let start = a_start.max(b_start);
+            let end = a_end.min(b_end);
+            if start <= end {
+                intersection.ranges.insert(start, end);
+            }
+            // Whichever range ends first can't overlap anything else in the other set.
+            if a_end < b_end {
+                ours.next();
+            } else {
+                theirs.next();
+            }
+        }
+        intersection
+    }
+
+    /// Return the set of values in `self` but not `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut difference = self.clone();
+        for range in other.ranges() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// Return the set of values of `T` which aren't in `self`.
+    ///
+    /// ```
+    /// # use util::range::IntervalSet;
+    /// let set: IntervalSet<u8> = [0..=9, 200..=250].into_iter().collect();
+    /// assert_eq!(set.complement().ranges().collect::<Vec<_>>(), vec![10..=199, 251..=255]);
+    /// ```
+    pub fn complement(&self) -> IntervalSet<T> {
+        let mut complement = IntervalSet::new();
+        // The first value which might not be in `self`, or `None` once `T::MAX` is covered.
+        let mut gap_start = Some(T::MIN);
+        for (start, end) in self.ranges.iter() {
+            if let (Some(from), Some(to)) = (gap_start, start.predecessor())
+                && from <= to
+            {
+                complement.ranges.insert(from, to);
+            }
+            gap_start = end.successor();
+        }
+        if let Some(from) = gap_start {
+            complement.ranges.insert(from, T::MAX);
+        }
+        complement
+    }
+}
+
+impl<T: Step> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Step> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Step + fmt::Debug> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.ranges()).finish()
     }
 }
@@ -1,24 +1,30 @@
-use std::fs::File;
-use std::io::{BufReader, Write};
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+use std::io::Write;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    Problem,
-    aoc::{Aoc, AocResult},
+    Problem, SolveFn,
+    aoc::{Aoc, AocResult, Submission},
+    cache::{CachedSolution, fingerprint},
+    history::{self, HistoryRecord},
+    input,
+    mem::{self, AllocStats},
+    submit::{self, SubmitGuards},
 };
 
-pub type SolveFn = fn(BufReader<File>) -> anyhow::Result<String>;
-
 /// Problem-specific parameters that can plug into an otherwise generic solution runner.
 pub struct RunArgs {
     /// The solution implementation function ([`SolveFn`]) to run.
     ///
     /// Example:
     /// ```
-    /// # use std::fs::File;
-    /// # use std::io::{BufRead, BufReader};
-    /// fn solve(reader: BufReader<File>) -> anyhow::Result<String> {
-    ///     for line in reader.lines() {
+    /// # use std::io::BufRead;
+    /// fn solve(input: &[u8]) -> anyhow::Result<String> {
+    ///     for line in input.lines() {
     ///         tracing::info!("{line:?}");
     ///     }
     ///     Ok("".into())
@@ -29,44 +35,258 @@ pub struct RunArgs {
     /// The [`Problem`] whose solution is being run (e.g. day1-1, day3-2).
     pub problem: Problem,
 
+    /// The custom input name (e.g. `test` for `day1/day1.test.input`), or `None` for the default
+    /// input.
+    pub input: Option<String>,
+
     /// The input filepath (e.g. `day1/day1.input`).
     pub input_filepath: PathBuf,
 
     /// The filepath where a cached solution may be saved (e.g. `day1/day1-1.solution`)
     pub solution_filepath: PathBuf,
 
+    /// The filepath where a record of each run is appended (e.g. `day1/history.jsonl`).
+    pub history_filepath: PathBuf,
+
     /// Advent of Code client. Will submit solutions if set.
     pub aoc_client: Option<Aoc>,
+
+    /// Show what would be submitted to AoC instead of submitting it.
+    pub dry_run: bool,
+
+    /// Checks a solution must pass before it's submitted; see [`crate::submit`].
+    pub submit_guards: SubmitGuards,
+
+    /// Whether to normalize line endings and remove trailing blank lines from the input before
+    /// running the solution; see [`crate::input::normalize`].
+    pub normalize: bool,
+
+    /// Whether to collect allocation statistics for the solution function; see [`crate::mem`].
+    pub mem: bool,
+}
+
+/// Whether a solution's output is known to be correct.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    /// Matches the cached solution, or was accepted by AoC.
+    Correct,
+
+    /// Doesn't match the cached solution, or was rejected by AoC.
+    Incorrect,
+
+    /// There was nothing to compare against.
+    Unverified,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::Unverified => write!(f, "unverified"),
+        }
+    }
+}
+
+/// The outcome of [`run`].
+#[derive(Debug, Clone)]
+pub struct RunReport {
+    /// The [`Problem`] whose solution was run.
+    pub problem: Problem,
+
+    /// The solution's output.
+    pub solution: String,
+
+    /// Whether `solution` is known to be correct.
+    pub verdict: Verdict,
+
+    /// How long the solution function took to run.
+    pub duration: Duration,
+
+    /// Allocation statistics for the solution function, if they were requested and available.
+    pub alloc_stats: Option<AllocStats>,
 }
 
-/// Run a solution function according to [`RunArgs`].
-pub fn run(args: &RunArgs) -> anyhow::Result<String> {
-    let input_file = File::open(&args.input_filepath)?;
-    let reader = BufReader::new(input_file);
+/// Run a solution function according to [`RunArgs`] on `input_bytes`, the contents of
+/// `args.input_filepath`.
+///
+/// Each run is appended to `args.history_filepath`; see [`crate::history`].
+pub fn run(args: &RunArgs, input_bytes: &[u8]) -> anyhow::Result<RunReport> {
+    let input_hash = fingerprint(input_bytes);
+    let input = if args.normalize {
+        normalize_input(args, input_bytes)
+    } else {
+        Cow::Borrowed(input_bytes)
+    };
+
+    if args.mem && !mem::installed() {
+        tracing::warn!("Allocation statistics unavailable; rebuild with `--features util/mem`");
+    }
 
     tracing::info!("Running solution on `{:?}`", &args.input_filepath);
-    let solution = (args.solve_fn)(reader)?;
-    tracing::info!("Solution finished: {solution}");
+    let start = Instant::now();
+    let solve_span = tracing::info_span!("solve", problem = %args.problem).entered();
+    let (solution, alloc_stats) = if args.mem {
+        mem::measure(|| (args.solve_fn)(&input))
+    } else {
+        ((args.solve_fn)(&input), None)
+    };
+    solve_span.exit();
+    let duration = start.elapsed();
+    let solution = solution?;
+    match &alloc_stats {
+        Some(alloc_stats) => {
+            tracing::info!("Solution finished in {duration:.2?} ({alloc_stats}): {solution}")
+        }
+        None => tracing::info!("Solution finished in {duration:.2?}: {solution}"),
+    }
 
-    if let Ok(cached_solution) = std::fs::read_to_string(&args.solution_filepath) {
+    let mut verdict = Verdict::Unverified;
+    if let Some(cached_solution) = CachedSolution::load_if_exists(&args.solution_filepath)? {
         tracing::info!("Cached solution found in `{:?}`", &args.solution_filepath);
-        let cached_solution = cached_solution.trim();
-        if cached_solution == solution {
-            tracing::info!("Correct! (`{}` == `{}`)", solution, cached_solution);
-        } else {
-            tracing::error!("Incorrect! (`{}` != `{}`)", solution, cached_solution);
-        }
-    } else if let Some(aoc) = &args.aoc_client {
-        tracing::info!("Submitting solution to AOC");
-        let aoc_result = aoc.submit(&args.problem, &solution)?;
-        if aoc_result == AocResult::Correct {
-            tracing::info!("Correct! (`{}`)", solution);
-            let mut file = File::create(&args.solution_filepath)?;
-            write!(file, "{}", &solution)?;
+        let cached_answer = &cached_solution.answer;
+        if !cached_solution.matches_input(&input_hash) {
+            tracing::warn!(
+                "`{:?}` has changed since the cached solution was saved; skipping comparison",
+                &args.input_filepath
+            );
+        } else if *cached_answer == solution {
+            tracing::info!("Correct! (`{}` == `{}`)", solution, cached_answer);
+            verdict = Verdict::Correct;
         } else {
-            tracing::error!("Incorrect! (`{}`)", solution);
+            tracing::error!("Incorrect! (`{}` != `{}`)", solution, cached_answer);
+            verdict = Verdict::Incorrect;
         }
+    } else if args.dry_run || args.aoc_client.is_some() {
+        verdict = submit(args, &solution, &input_hash)?;
     }
 
-    Ok(solution)
+    let report = RunReport {
+        problem: args.problem,
+        solution,
+        verdict,
+        duration,
+        alloc_stats,
+    };
+
+    let working_dir = args.history_filepath.parent().unwrap_or(".".as_ref());
+    let record = HistoryRecord::new(&report, &args.input, working_dir);
+    if let Err(e) = history::append(&args.history_filepath, &record) {
+        tracing::warn!(
+            "Failed to record run in `{:?}`: {e}",
+            &args.history_filepath
+        );
+    }
+
+    Ok(report)
+}
+
+/// Normalize the input according to [`crate::input::normalize`], logging a warning if that changes
+/// it.
+///
+/// The cached solution and history still refer to the original input, so its fingerprint is
+/// unaffected.
+fn normalize_input<'a>(args: &RunArgs, input_bytes: &'a [u8]) -> Cow<'a, [u8]> {
+    let Ok(text) = std::str::from_utf8(input_bytes) else {
+        tracing::warn!(
+            "`{:?}` isn't valid UTF-8; skipping normalization",
+            &args.input_filepath
+        );
+        return Cow::Borrowed(input_bytes);
+    };
+    let normalized = input::normalize(text);
+    if !normalized.changed() {
+        return Cow::Borrowed(input_bytes);
+    }
+    tracing::warn!(
+        "Normalized `{:?}`: replaced {} CRLF line endings, removed {} trailing blank lines",
+        &args.input_filepath,
+        normalized.crlf_line_endings,
+        normalized.trailing_blank_lines,
+    );
+    Cow::Owned(normalized.text.into_bytes())
+}
+
+/// Submit `solution` to AoC (or show what would be submitted, for a dry run) if it passes
+/// `args.submit_guards`. Correct solutions are cached in `args.solution_filepath`.
+fn submit(args: &RunArgs, solution: &str, input_hash: &str) -> anyhow::Result<Verdict> {
+    submit::check(solution, &args.submit_guards)?;
+    let submission = Submission::new(&args.problem, solution);
+
+    let Some(aoc) = args.aoc_client.as_ref().filter(|_| !args.dry_run) else {
+        eprintln!("Dry run; not submitting:\n{submission}");
+        return Ok(Verdict::Unverified);
+    };
+    if args.submit_guards.confirm && !submit::confirm(&submission)? {
+        tracing::warn!("Not submitting `{solution}`");
+        return Ok(Verdict::Unverified);
+    }
+
+    tracing::info!("Submitting solution to AOC");
+    let aoc_result = aoc.submit(&args.problem, solution)?;
+    if aoc_result == AocResult::Correct {
+        tracing::info!("Correct! (`{}`)", solution);
+        CachedSolution::new(solution, input_hash, aoc.session_fingerprint())
+            .save(&args.solution_filepath)?;
+        Ok(Verdict::Correct)
+    } else {
+        tracing::error!("Incorrect! (`{}`)", solution);
+        Ok(Verdict::Incorrect)
+    }
+}
+
+/// Print a table summarizing several runs (e.g. both parts of a day) to stderr. Runs which failed
+/// are shown with their error in place of an answer.
+pub fn print_reports(results: &[(Problem, anyhow::Result<RunReport>)]) -> anyhow::Result<()> {
+    let mut stderr = std::io::stderr().lock();
+    let show_mem = results.iter().any(|(_, result)| {
+        result
+            .as_ref()
+            .is_ok_and(|report| report.alloc_stats.is_some())
+    });
+    write!(
+        stderr,
+        "{:<10} {:>12} {:<10} ",
+        "PROBLEM", "TIME", "VERDICT"
+    )?;
+    if show_mem {
+        write!(stderr, "{:<50} ", "MEMORY")?;
+    }
+    writeln!(stderr, "ANSWER")?;
+    for (problem, result) in results {
+        let report = match result {
+            Ok(report) => report,
+            Err(e) => {
+                write!(
+                    stderr,
+                    "{:<10} {:>12} {:<10} ",
+                    problem.to_string(),
+                    "-",
+                    "failed"
+                )?;
+                if show_mem {
+                    write!(stderr, "{:<50} ", "")?;
+                }
+                writeln!(stderr, "error: {e:#}")?;
+                continue;
+            }
+        };
+        write!(
+            stderr,
+            "{:<10} {:>12} {:<10} ",
+            report.problem.to_string(),
+            format!("{:.2?}", report.duration),
+            report.verdict.to_string(),
+        )?;
+        if show_mem {
+            let alloc_stats = report
+                .alloc_stats
+                .map(|alloc_stats| alloc_stats.to_string())
+                .unwrap_or_default();
+            write!(stderr, "{alloc_stats:<50} ")?;
+        }
+        writeln!(stderr, "{}", report.solution)?;
+    }
+    Ok(())
 }
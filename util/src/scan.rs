@@ -0,0 +1,387 @@
+use std::any::type_name;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// Derive [`FromStr`] (with [`ScanError`] as the error) and [`Scan`] from an
+/// `#[aoc(pattern = "...")]` attribute, matched the same way as [`scan`].
+///
+/// In a struct's pattern, `{name}` is a named field and `{0}` is a tuple field. `{}` takes fields
+/// in declaration order. Every field must appear exactly once, and is parsed with its own
+/// [`Scan`] impl. Errors name the field which failed to parse.
+///
+/// ```
+/// # use util::scan::AocParse;
+/// #[derive(AocParse, Debug, PartialEq)]
+/// #[aoc(pattern = "{name} can fly {speed} km/s")]
+/// struct Reindeer {
+///     name: String,
+///     speed: u32,
+/// }
+///
+/// let comet: Reindeer = "Comet can fly 14 km/s".parse().unwrap();
+/// assert_eq!(comet, Reindeer { name: "Comet".into(), speed: 14 });
+///
+/// let error = "Comet can fly fast km/s".parse::<Reindeer>().unwrap_err();
+/// assert_eq!(error.field.as_deref(), Some("Reindeer.speed"));
+/// assert_eq!(error.column, 15);
+/// ```
+///
+/// Each enum variant has its own pattern. Variants are tried in order of the literal their pattern
+/// starts with, longest first, skipping those whose literal doesn't match; if a variant fails to
+/// parse, the next one is tried. Unit variants without a pattern match their own name.
+///
+/// ```
+/// # use util::scan::AocParse;
+/// #[derive(AocParse, Debug, PartialEq)]
+/// enum Direction {
+///     #[aoc(pattern = "L")]
+///     Left,
+///     #[aoc(pattern = "R")]
+///     Right,
+/// }
+///
+/// #[derive(AocParse, Debug, PartialEq)]
+/// enum Instruction {
+///     #[aoc(pattern = "turn {}{}")]
+///     Turn(Direction, u16),
+///     #[aoc(pattern = "wait")]
+///     Wait,
+/// }
+///
+/// assert_eq!("turn L68".parse(), Ok(Instruction::Turn(Direction::Left, 68)));
+/// assert_eq!("wait".parse(), Ok(Instruction::Wait));
+///
+/// let error = "turn U3".parse::<Instruction>().unwrap_err();
+/// assert_eq!(error.field.as_deref(), Some("Instruction::Turn.0"));
+/// assert_eq!(error.column, 6);
+/// assert!(error.message.starts_with(r#"cannot parse "U3""#));
+///
+/// let error = "jump".parse::<Instruction>().unwrap_err();
+/// assert_eq!(error.message, r#"expected one of "turn ", "wait""#);
+/// ```
+pub use util_derive::AocParse;
+
+/// A pattern or line which didn't match in [`scan`]. `column` counts characters from 1.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub struct ScanError {
+    pub line: String,
+    pub column: usize,
+
+    /// The field being parsed when matching failed, if it has a name. Set by
+    /// [`Scanner::named_field`], which is what [`AocParse`] uses.
+    pub field: Option<String>,
+
+    pub message: String,
+}
+
+impl ScanError {
+    pub fn new(line: &str, column: usize, message: impl Display) -> ScanError {
+        ScanError {
+            line: line.into(),
+            column,
+            field: None,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "column {} of {:?}", self.column, self.line)?;
+        if let Some(field) = &self.field {
+            write!(f, " in field `{field}`")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Types which can be pulled out of a line by [`scan`].
+///
+/// Fields are parsed with [`FromStr`]. When a field is followed by a literal in the pattern, its
+/// text runs up to that literal. Otherwise (e.g. `{}{}`), [`Scan::token_len`] decides where it
+/// ends.
+pub trait Scan: FromStr {
+    /// How many bytes at the start of `s` belong to this field when the pattern doesn't say where
+    /// it ends. Defaults to the rest of the line.
+    fn token_len(s: &str) -> usize {
+        s.len()
+    }
+}
+
+/// The length of an optionally signed run of ASCII digits at the start of `s`.
+fn integer_len(s: &str) -> usize {
+    let sign_len = usize::from(s.starts_with(['-', '+']));
+    sign_len
+        + s[sign_len..]
+            .bytes()
+            .take_while(|b| b.is_ascii_digit())
+            .count()
+}
+
+macro_rules! impl_scan_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Scan for $t {
+                fn token_len(s: &str) -> usize {
+                    integer_len(s)
+                }
+            }
+        )*
+    };
+}
+
+impl_scan_integer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+impl Scan for f32 {
+    fn token_len(s: &str) -> usize {
+        s.bytes()
+            .take_while(|b| b.is_ascii_digit() || b"+-.eE".contains(b))
+            .count()
+    }
+}
+
+impl Scan for f64 {
+    fn token_len(s: &str) -> usize {
+        f32::token_len(s)
+    }
+}
+
+impl Scan for char {
+    fn token_len(s: &str) -> usize {
+        s.chars().next().map_or(0, char::len_utf8)
+    }
+}
+
+impl Scan for String {}
+
+/// A piece of a [`scan`] pattern.
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field,
+}
+
+/// Split a pattern into literals and `{}` fields. `{{` and `}}` are literal braces.
+fn parse_pattern(pattern: &str) -> Vec<Segment> {
+    let mut segments = vec![];
+    let mut literal = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('}')) => {
+                chars.next();
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field);
+            }
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                literal.push(c);
+            }
+            _ => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    segments
+}
+
+/// Matches a line against a pattern one field at a time. Used by [`ScanFields`] impls.
+pub struct Scanner<'a> {
+    line: &'a str,
+
+    /// Byte offset of the next unmatched character in `line`.
+    position: usize,
+
+    segments: Vec<Segment>,
+
+    /// Index of the next unmatched segment.
+    segment: usize,
+}
+
+impl<'a> Scanner<'a> {
+    /// Prepare to match `line` against `pattern`, where each `{}` is a field.
+    pub fn new(line: &'a str, pattern: &str) -> Scanner<'a> {
+        Scanner {
+            line,
+            position: 0,
+            segments: parse_pattern(pattern),
+            segment: 0,
+        }
+    }
+
+    fn error(&self, message: impl Display) -> ScanError {
+        let column = self.line[..self.position].chars().count() + 1;
+        ScanError::new(self.line, column, message)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.line[self.position..]
+    }
+
+    /// Match literal segments until the next field or the end of the pattern.
+    fn match_literals(&mut self) -> Result<(), ScanError> {
+        while let Some(Segment::Literal(literal)) = self.segments.get(self.segment) {
+            if !self.rest().starts_with(literal.as_str()) {
+                return Err(self.error(format_args!("expected {literal:?}")));
+            }
+            self.position += literal.len();
+            self.segment += 1;
+        }
+        Ok(())
+    }
+
+    /// Match the next field in the pattern and parse it as a `T`.
+    pub fn field<T: Scan>(&mut self) -> Result<T, ScanError> {
+        self.match_literals()?;
+        if self.segments.get(self.segment) != Some(&Segment::Field) {
+            return Err(self.error("pattern has fewer `{}` fields than expected"));
+        }
+        self.segment += 1;
+
+        let rest = self.rest();
+        let len = match self.segments.get(self.segment) {
+            Some(Segment::Literal(literal)) => rest
+                .find(literal.as_str())
+                .ok_or_else(|| self.error(format_args!("expected {literal:?} after field")))?,
+            Some(Segment::Field) => T::token_len(rest),
+            None => rest.len(),
+        };
+        let text = &rest[..len];
+        let value = text.parse().map_err(|_| {
+            self.error(format_args!(
+                "cannot parse {text:?} as `{}`",
+                type_name::<T>()
+            ))
+        })?;
+        self.position += len;
+        Ok(value)
+    }
+
+    /// Like [`Scanner::field`], but errors while parsing the field are labelled with `name`.
+    pub fn named_field<T: Scan>(&mut self, name: &str) -> Result<T, ScanError> {
+        self.match_literals()?;
+        self.field().map_err(|error| ScanError {
+            field: Some(name.into()),
+            ..error
+        })
+    }
+
+    /// Match any trailing literals, and check that the whole line and pattern were used.
+    pub fn finish(mut self) -> Result<(), ScanError> {
+        self.match_literals()?;
+        if self.segment < self.segments.len() {
+            return Err(self.error("pattern has more `{}` fields than expected"));
+        }
+        if !self.rest().is_empty() {
+            return Err(self.error(format_args!("unexpected {:?}", self.rest())));
+        }
+        Ok(())
+    }
+}
+
+/// Tuples of [`Scan`] types, which [`scan`] fills in order from a pattern's `{}` fields.
+pub trait ScanFields: Sized {
+    fn scan_fields(scanner: &mut Scanner) -> Result<Self, ScanError>;
+}
+
+macro_rules! impl_scan_fields {
+    ($($t:ident),+) => {
+        impl<$($t: Scan),+> ScanFields for ($($t,)+) {
+            fn scan_fields(scanner: &mut Scanner) -> Result<Self, ScanError> {
+                Ok(($(scanner.field::<$t>()?,)+))
+            }
+        }
+    };
+}
+
+impl_scan_fields!(A);
+impl_scan_fields!(A, B);
+impl_scan_fields!(A, B, C);
+impl_scan_fields!(A, B, C, D);
+impl_scan_fields!(A, B, C, D, E);
+impl_scan_fields!(A, B, C, D, E, F);
+impl_scan_fields!(A, B, C, D, E, F, G);
+impl_scan_fields!(A, B, C, D, E, F, G, H);
+
+/// Match `line` against `pattern`, where each `{}` is a field, and parse the fields into a tuple.
+/// Usually called through [`scan!`](crate::scan!).
+pub fn scan<T: ScanFields>(line: &str, pattern: &str) -> Result<T, ScanError> {
+    let mut scanner = Scanner::new(line, pattern);
+    let fields = T::scan_fields(&mut scanner)?;
+    scanner.finish()?;
+    Ok(fields)
+}
+
+/// Iterate over every integer in `text`, with its sign. A `-` is treated as a sign unless it
+/// follows a letter or digit, so `3-5` is two positive integers.
+pub(crate) fn integer_spans(text: &str) -> impl Iterator<Item = &str> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == bytes.len() {
+            return None;
+        }
+        let mut start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if start > 0
+            && bytes[start - 1] == b'-'
+            && !(start > 1 && bytes[start - 2].is_ascii_alphanumeric())
+        {
+            start -= 1;
+        }
+        Some(&text[start..i])
+    })
+}
+
+/// Extract every integer from `line`, with its sign. A `-` is treated as a sign unless it follows a
+/// letter or digit. Integers which don't fit in an `i64` are skipped.
+///
+/// ```
+/// # use util::scan::ints;
+/// assert_eq!(ints("p=0,4 v=3,-3"), vec![0, 4, 3, -3]);
+/// assert_eq!(ints("3-5"), vec![3, 5]);
+/// assert!(ints("no numbers").is_empty());
+/// ```
+pub fn ints(line: &str) -> Vec<i64> {
+    integer_spans(line)
+        .filter_map(|span| span.parse().ok())
+        .collect()
+}
+
+/// Pull typed fields out of a line with a pattern, where each `{}` is a field. Returns a
+/// `Result` of a tuple, whose types are either inferred or listed after the pattern.
+///
+/// ```
+/// # use util::scan;
+/// let (dir, dist): (char, u16) = scan!("L68", "{}{}").unwrap();
+/// assert_eq!((dir, dist), ('L', 68));
+///
+/// let (x, y, name) = scan!("pos=<-3,14> probe", "pos=<{},{}> {}", i32, i32, String).unwrap();
+/// assert_eq!((x, y, name.as_str()), (-3, 14, "probe"));
+///
+/// let error = scan!("pos=<-3;14>", "pos=<{},{}>", i32, i32).unwrap_err();
+/// assert_eq!(error.column, 6);
+/// assert_eq!(error.to_string(), r#"column 6 of "pos=<-3;14>": expected "," after field"#);
+///
+/// let error = scan!("L6x", "{}{}", char, u16).unwrap_err();
+/// assert_eq!(error.column, 2);
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($line:expr, $pattern:expr $(,)?) => {
+        $crate::scan::scan($line, $pattern)
+    };
+    ($line:expr, $pattern:expr, $($t:ty),+ $(,)?) => {
+        $crate::scan::scan::<($($t,)+)>($line, $pattern)
+    };
+}
@@ -0,0 +1,120 @@
+use std::io::{BufRead, IsTerminal, Write};
+
+use crate::aoc::Submission;
+
+/// The custom input name for a day's example input (e.g. `day1/day1.test.input`). Its cached
+/// solution (e.g. `day1/day1-1.test.solution`) is the known example answer.
+pub const EXAMPLE_INPUT: &str = "test";
+
+/// Reasons a solution won't be submitted to AoC.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum SubmitError {
+    #[error("refusing to submit an empty answer")]
+    Empty,
+
+    #[error("refusing to submit an answer with a line break: {0:?}")]
+    MultipleLines(String),
+
+    #[error("refusing to submit non-numeric answer `{0}`; pass `--allow-non-numeric` to submit it")]
+    NotNumeric(String),
+
+    #[error("refusing to submit `{0}`, which is usually a bug; pass `--allow-zero` to submit it")]
+    Zero(String),
+
+    #[error("refusing to submit `{0}`, which is the answer for the example input")]
+    MatchesExample(String),
+}
+
+/// Checks applied to a solution before it is submitted to AoC.
+#[derive(Clone, Debug, Default)]
+pub struct SubmitGuards {
+    /// Allow answers which aren't integers.
+    pub allow_non_numeric: bool,
+
+    /// Allow an answer of zero.
+    pub allow_zero: bool,
+
+    /// The known answer for the example input, if there is one. Real inputs almost never share it.
+    pub example_answer: Option<String>,
+
+    /// Ask for confirmation before submitting if stdout is a terminal.
+    pub confirm: bool,
+}
+
+/// Whether `answer` is an integer, optionally negative.
+fn is_numeric(answer: &str) -> bool {
+    let digits = answer.strip_prefix('-').unwrap_or(answer);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Whether `answer` is an integer equal to zero (e.g. `0`, `-0` or `00`).
+fn is_zero(answer: &str) -> bool {
+    is_numeric(answer) && answer.bytes().all(|b| b == b'-' || b == b'0')
+}
+
+/// Check that `answer` looks like something AoC could accept.
+///
+/// ```
+/// # use util::submit::{SubmitError, SubmitGuards, check};
+/// let guards = SubmitGuards {
+///     example_answer: Some("3".into()),
+///     ..Default::default()
+/// };
+/// assert_eq!(check("1182", &guards), Ok(()));
+/// assert_eq!(check("-17", &guards), Ok(()));
+/// assert_eq!(check("", &guards), Err(SubmitError::Empty));
+/// assert_eq!(check("0", &guards), Err(SubmitError::Zero("0".into())));
+/// assert_eq!(check("3", &guards), Err(SubmitError::MatchesExample("3".into())));
+/// assert_eq!(
+///     check("Some(12)", &guards),
+///     Err(SubmitError::NotNumeric("Some(12)".into()))
+/// );
+/// assert!(matches!(check("1\n2", &guards), Err(SubmitError::MultipleLines(_))));
+///
+/// let allow_zero = SubmitGuards {
+///     allow_zero: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(check("0", &allow_zero), Ok(()));
+///
+/// // A trailing line break would be submitted too.
+/// let allow_non_numeric = SubmitGuards {
+///     allow_non_numeric: true,
+///     ..Default::default()
+/// };
+/// assert!(matches!(check("1\n", &allow_non_numeric), Err(SubmitError::MultipleLines(_))));
+/// assert!(matches!(check("1\r\n", &allow_non_numeric), Err(SubmitError::MultipleLines(_))));
+/// ```
+pub fn check(answer: &str, guards: &SubmitGuards) -> Result<(), SubmitError> {
+    if answer.trim().is_empty() {
+        return Err(SubmitError::Empty);
+    }
+    if answer.contains(['\n', '\r']) {
+        return Err(SubmitError::MultipleLines(answer.into()));
+    }
+    if !guards.allow_non_numeric && !is_numeric(answer) {
+        return Err(SubmitError::NotNumeric(answer.into()));
+    }
+    if !guards.allow_zero && is_zero(answer) {
+        return Err(SubmitError::Zero(answer.into()));
+    }
+    if guards.example_answer.as_deref() == Some(answer) {
+        return Err(SubmitError::MatchesExample(answer.into()));
+    }
+    Ok(())
+}
+
+/// Show `submission` and ask whether to send it. Always returns `true` if stdout isn't a terminal
+/// (e.g. the answer is being piped), since nobody would see the prompt.
+pub fn confirm(submission: &Submission) -> std::io::Result<bool> {
+    if !std::io::stdout().is_terminal() {
+        return Ok(true);
+    }
+    let mut stderr = std::io::stderr().lock();
+    write!(stderr, "{submission}\nSubmit? [y/N] ")?;
+    stderr.flush()?;
+
+    let mut reply = String::new();
+    std::io::stdin().lock().read_line(&mut reply)?;
+    Ok(matches!(reply.trim(), "y" | "Y" | "yes"))
+}
@@ -0,0 +1,127 @@
+use std::io::ErrorKind;
+
+use crate::Solution;
+use crate::cache::{CachedSolution, fingerprint};
+
+/// Run `solution` on `example` and check that it returns `expected`. Used by
+/// [`aoc_tests!`](crate::aoc_tests!).
+pub fn check_example(solution: &Solution, example: &str, expected: &str) -> anyhow::Result<()> {
+    let answer = (solution.solve_fn)(example.as_bytes())?;
+    assert_eq!(answer, expected, "{} on the example", solution.problem);
+    Ok(())
+}
+
+/// Run `solution` on its real input and check the answer against its cached solution. Skipped
+/// (with a message on stderr) if either file is missing, or if the input has changed since the
+/// solution was cached. Fails if either file can't be read, e.g. if the cached solution is
+/// corrupt. Used by [`aoc_tests!`](crate::aoc_tests!).
+pub fn check_input(solution: &Solution) -> anyhow::Result<()> {
+    let input_filepath = solution.input_file(&None);
+    let solution_filepath = solution.solution_file(&None);
+    let input_bytes = match std::fs::read(&input_filepath) {
+        Ok(input_bytes) => input_bytes,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!(
+                "skipping {}: no input at `{}`",
+                solution.problem,
+                input_filepath.display()
+            );
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let Some(cached_solution) = CachedSolution::load_if_exists(&solution_filepath)? else {
+        eprintln!(
+            "skipping {}: no cached solution at `{}`",
+            solution.problem,
+            solution_filepath.display()
+        );
+        return Ok(());
+    };
+    if !cached_solution.matches_input(&fingerprint(&input_bytes)) {
+        eprintln!(
+            "skipping {}: `{}` has changed since the cached solution was saved",
+            solution.problem,
+            input_filepath.display()
+        );
+        return Ok(());
+    }
+
+    let answer = (solution.solve_fn)(&input_bytes)?;
+    assert_eq!(
+        answer,
+        cached_solution.answer,
+        "{} on `{}`",
+        solution.problem,
+        input_filepath.display()
+    );
+    Ok(())
+}
+
+/// Generate tests for a day's solutions. Each part gets an `example` test, which runs `solve()` on
+/// the example input and checks the expected answer, and an `input` test, which runs `solve()` on
+/// `dayX/dayX.input` and checks it against `dayX/dayX-Y.solution` (see
+/// [`Solution::solution_file`]). The `input` test passes without doing anything (besides noting it
+/// on stderr) if either file is missing, since inputs and answers aren't checked in; see
+/// [`check_input`](crate::testing::check_input).
+///
+/// It belongs in the day's library, next to the `part_1` and `part_2` modules (see
+/// [`solution!`](crate::solution!)):
+/// ```ignore
+/// pub mod part_1;
+/// pub mod part_2;
+///
+/// util::aoc_tests! {
+///     example: "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n",
+///     part_1: "3",
+///     part_2: "6",
+/// }
+/// ```
+///
+/// If a part has its own example, it can be given with the answer:
+/// ```ignore
+/// util::aoc_tests! {
+///     example: "...",
+///     part_1: "3",
+///     part_2: ("...", "6"),
+/// }
+/// ```
+#[macro_export]
+macro_rules! aoc_tests {
+    (example: $example:expr, $($part:ident: $expected:tt),+ $(,)?) => {
+        #[cfg(test)]
+        mod aoc_tests {
+            $($crate::aoc_tests!(@part $part $example, $expected);)+
+        }
+    };
+    (@part $part:ident $example:expr, ($part_example:expr, $expected:expr)) => {
+        $crate::aoc_tests!(@part $part $part_example, $expected);
+    };
+    (@part part_1 $example:expr, $expected:expr) => {
+        $crate::aoc_tests!(@tests part_1 "-1" $example, $expected);
+    };
+    (@part part_2 $example:expr, $expected:expr) => {
+        $crate::aoc_tests!(@tests part_2 "-2" $example, $expected);
+    };
+    (@tests $part:ident $suffix:literal $example:expr, $expected:expr) => {
+        mod $part {
+            fn solution() -> $crate::Solution {
+                super::super::$part::solution(
+                    concat!(env!("CARGO_PKG_NAME"), $suffix)
+                        .parse()
+                        .expect("day packages are named like `day1`"),
+                )
+            }
+
+            #[test]
+            fn example() -> anyhow::Result<()> {
+                $crate::testing::check_example(&solution(), $example, $expected)
+            }
+
+            #[test]
+            fn input() -> anyhow::Result<()> {
+                $crate::testing::check_input(&solution())
+            }
+        }
+    };
+}
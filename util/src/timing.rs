@@ -0,0 +1,230 @@
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tracing::Subscriber;
+use tracing::span::{Attributes, Id};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// Small, stable thread IDs for trace events. `std::thread::ThreadId` can't be converted to an
+    /// integer on stable Rust.
+    static THREAD_ID: Cell<u64> = Cell::new(NEXT_THREAD_ID.fetch_add(1, Relaxed));
+}
+
+/// Timing data stored in each span's extensions while the span is open.
+#[derive(Default)]
+struct SpanTiming {
+    /// Time spent inside the span so far.
+    busy: Duration,
+
+    /// When the span was most recently entered, if it's currently entered.
+    entered_at: Option<Instant>,
+}
+
+/// Aggregated timings for every span with the same path from the root span (e.g. every
+/// `solve > import` span).
+#[derive(Default)]
+struct SpanNode {
+    /// How many spans with this path were closed.
+    calls: usize,
+
+    /// Total time spent inside spans with this path, including time spent in child spans.
+    busy: Duration,
+
+    children: BTreeMap<&'static str, SpanNode>,
+}
+
+impl SpanNode {
+    fn print(&self, name: &str, depth: usize, out: &mut impl Write) -> std::io::Result<()> {
+        let children_busy: Duration = self.children.values().map(|child| child.busy).sum();
+        writeln!(
+            out,
+            "{:<40} {:>8} {:>12} {:>12}",
+            format!("{:indent$}{name}", "", indent = depth * 2),
+            self.calls,
+            format!("{:.2?}", self.busy),
+            format!("{:.2?}", self.busy.saturating_sub(children_busy)),
+        )?;
+        for (child_name, child) in self.children.iter() {
+            child.print(child_name, depth + 1, out)?;
+        }
+        Ok(())
+    }
+}
+
+/// A completed span, in the Chrome trace event format.
+///
+/// See <https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU>.
+#[derive(Serialize)]
+struct TraceEvent {
+    name: &'static str,
+    cat: &'static str,
+    ph: &'static str,
+    /// Start time in microseconds.
+    ts: f64,
+    /// Duration in microseconds.
+    dur: f64,
+    pid: u32,
+    tid: u64,
+}
+
+#[derive(Default)]
+struct Recorded {
+    root: SpanNode,
+    events: Vec<TraceEvent>,
+}
+
+/// Span timings collected by a [`SpanTimingLayer`].
+#[derive(Clone, Default)]
+pub struct SpanTimings {
+    recorded: Arc<Mutex<Recorded>>,
+}
+
+impl SpanTimings {
+    /// Print a hierarchical summary of how much time was spent in each span. The `SELF` column
+    /// excludes time spent in child spans.
+    pub fn print_summary(&self, out: &mut impl Write) -> std::io::Result<()> {
+        let recorded = self.recorded.lock().unwrap_or_else(|e| e.into_inner());
+        writeln!(
+            out,
+            "{:<40} {:>8} {:>12} {:>12}",
+            "SPAN", "CALLS", "TOTAL", "SELF"
+        )?;
+        for (name, node) in recorded.root.children.iter() {
+            node.print(name, 0, out)?;
+        }
+        Ok(())
+    }
+
+    /// Write every recorded span to `filepath` as a Chrome trace event JSON file, which can be
+    /// loaded into trace viewers like Perfetto or `chrome://tracing`.
+    pub fn write_chrome_trace<P: AsRef<Path>>(&self, filepath: P) -> anyhow::Result<()> {
+        let recorded = self.recorded.lock().unwrap_or_else(|e| e.into_inner());
+        let mut file = BufWriter::new(File::create(filepath)?);
+        serde_json::to_writer(
+            &mut file,
+            &serde_json::json!({ "traceEvents": recorded.events }),
+        )?;
+        Ok(file.flush()?)
+    }
+}
+
+/// A [`Layer`] which records how long each span is entered for.
+///
+/// Spans are aggregated by their path from the root span, so the `import` span inside the
+/// `solve` span is tracked separately from an `import` span elsewhere. Use [`SpanTimings`] to
+/// print a summary or write a Chrome trace once the program is done.
+///
+/// Events are ignored, but the layer doesn't disable them, since that would hide them from every
+/// other layer of the subscriber too.
+pub struct SpanTimingLayer {
+    timings: SpanTimings,
+
+    /// When the layer was created. Trace event timestamps are relative to this.
+    start: Instant,
+
+    /// Whether to keep every entered span as a [`TraceEvent`], rather than only aggregates.
+    record_events: bool,
+}
+
+impl SpanTimingLayer {
+    /// Create a [`SpanTimingLayer`] and a handle to the timings it will record. If
+    /// `record_events` is set, every span will also be kept for
+    /// [`SpanTimings::write_chrome_trace`].
+    pub fn new(record_events: bool) -> (SpanTimingLayer, SpanTimings) {
+        let timings = SpanTimings::default();
+        let layer = SpanTimingLayer {
+            timings: timings.clone(),
+            start: Instant::now(),
+            record_events,
+        };
+        (layer, timings)
+    }
+}
+
+impl<S> Layer<S> for SpanTimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanTiming::default());
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>()
+        {
+            timing.entered_at = Some(Instant::now());
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        let Some(timing) = extensions.get_mut::<SpanTiming>() else {
+            return;
+        };
+        let Some(entered_at) = timing.entered_at.take() else {
+            return;
+        };
+        let elapsed = entered_at.elapsed();
+        timing.busy += elapsed;
+
+        if self.record_events {
+            let event = TraceEvent {
+                name: span.name(),
+                cat: span.metadata().target(),
+                ph: "X",
+                ts: entered_at.duration_since(self.start).as_secs_f64() * 1e6,
+                dur: elapsed.as_secs_f64() * 1e6,
+                pid: std::process::id(),
+                tid: THREAD_ID.with(|id| id.get()),
+            };
+            let mut recorded = self
+                .timings
+                .recorded
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            recorded.events.push(event);
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let busy = span
+            .extensions()
+            .get::<SpanTiming>()
+            .map(|timing| timing.busy)
+            .unwrap_or_default();
+
+        let mut recorded = self
+            .timings
+            .recorded
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let node = span
+            .scope()
+            .from_root()
+            .fold(&mut recorded.root, |node, span| {
+                node.children.entry(span.name()).or_default()
+            });
+        node.calls += 1;
+        node.busy += busy;
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::{Solution, history};
+
+/// How often to check watched files for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// ANSI escape sequence which clears the terminal and moves the cursor to the top left.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Modification times for every file under a set of watched paths.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Record the modification time of `path`, or of every file under `path` if it's a directory.
+/// Paths which don't exist are skipped, so deleting a watched file counts as a change.
+fn snapshot_path(path: &Path, snapshot: &mut Snapshot) {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return;
+    };
+    if metadata.is_dir() {
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            snapshot_path(&entry.path(), snapshot);
+        }
+    } else if let Ok(modified) = metadata.modified() {
+        snapshot.insert(path.to_path_buf(), modified);
+    }
+}
+
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for path in paths {
+        snapshot_path(path, &mut snapshot);
+    }
+    snapshot
+}
+
+/// Rebuild and rerun `solutions` with `cargo run` every time their `src/` directory or selected
+/// input file changes. All of `solutions` are expected to be from the same day. `solve_args` are
+/// passed on to the `solve` command along with `input`; see [`SolutionCli::watch_args`].
+///
+/// The rerun happens in a child process so that source changes are picked up. The child records
+/// each run in the day's history file (see [`crate::history`]), which is where the answers,
+/// verdicts and timings shown after each run come from.
+///
+/// [`SolutionCli::watch_args`]: crate::cli::SolutionCli::watch_args
+pub fn watch(
+    solutions: &[Solution],
+    input: &Option<String>,
+    solve_args: &[OsString],
+) -> anyhow::Result<()> {
+    let Some(first_solution) = solutions.first() else {
+        return Ok(());
+    };
+    // Part binaries are named after their problem (e.g. `day1-2`), and a binary which runs every
+    // part of a day is named after the day (e.g. `day1`).
+    let bin = match solutions {
+        [solution] => solution.problem.to_string(),
+        _ => format!("day{}", first_solution.problem.day),
+    };
+    let working_dir = &first_solution.working_dir;
+    let history_file = first_solution.history_file();
+    let watched = vec![working_dir.join("src"), first_solution.input_file(input)];
+
+    let mut last_snapshot = None;
+    loop {
+        let current_snapshot = snapshot(&watched);
+        if last_snapshot.as_ref() == Some(&current_snapshot) {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+        last_snapshot = Some(current_snapshot);
+
+        print!("{CLEAR_SCREEN}");
+        std::io::stdout().flush()?;
+
+        let runs_before = history::load(&history_file)?.len();
+        let mut command = Command::new(std::env::var_os("CARGO").unwrap_or("cargo".into()));
+        command.args(["run", "--quiet", "--bin", &bin]);
+        // Build the child with the same counting allocator, so `--mem` works the same way.
+        if cfg!(feature = "mem") {
+            command.args(["--features", "util/mem"]);
+        }
+        command
+            .args(["--", "solve"])
+            .args(solve_args)
+            .current_dir(working_dir);
+        if let Some(input) = input {
+            command.args(["--input", input]);
+        }
+
+        let start = Instant::now();
+        let status = command.status()?;
+        let elapsed = start.elapsed();
+
+        let records = history::load(&history_file)?;
+        let new_runs = records.get(runs_before..).unwrap_or_default();
+        println!();
+        for record in new_runs {
+            println!(
+                "{}: {} [{}] solved in {:.2?}",
+                record.problem,
+                record.answer,
+                record.verdict,
+                record.duration(),
+            );
+        }
+        if status.success() && !new_runs.is_empty() {
+            println!("{bin}: finished in {elapsed:.2?} including build");
+        } else {
+            println!("{bin}: failed ({status}) after {elapsed:.2?}");
+        }
+        println!(
+            "Watching {} for changes...",
+            watched
+                .iter()
+                .map(|path| format!("`{}`", path.display()))
+                .collect::<Vec<_>>()
+                .join(" and ")
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Tests for reading and writing [`CachedSolution`] files.
+
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use util::cache::{CachedSolution, fingerprint};
+
+/// A path in the temporary directory that no other test uses.
+fn scratch_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("util-cache-{}-{name}.solution", std::process::id()))
+}
+
+/// Write `contents` to a scratch file and load it as a cached solution.
+fn load(name: &str, contents: &str) -> CachedSolution {
+    let filepath = scratch_path(name);
+    std::fs::write(&filepath, contents).unwrap();
+    let cached_solution = CachedSolution::load(&filepath);
+    std::fs::remove_file(&filepath).unwrap();
+    cached_solution.unwrap()
+}
+
+#[test]
+fn save_and_load() {
+    let filepath = scratch_path("round-trip");
+    let cached_solution = CachedSolution::new("1227775554", &fingerprint(b"11-22"), "0123456789ab");
+    cached_solution.save(&filepath).unwrap();
+    let loaded = CachedSolution::load(&filepath);
+    std::fs::remove_file(&filepath).unwrap();
+
+    assert_eq!(loaded.unwrap(), cached_solution);
+}
+
+#[test]
+fn load_legacy_plain_text() {
+    let cached_solution = load("legacy", "1227775554\n");
+    assert_eq!(
+        cached_solution,
+        CachedSolution {
+            answer: "1227775554".into(),
+            input_hash: None,
+            submitted_at: None,
+            session_fingerprint: None,
+        }
+    );
+}
+
+#[test]
+fn load_json_without_optional_fields() {
+    let cached_solution = load("answer-only", r#"{"answer": "3"}"#);
+    assert_eq!(cached_solution.answer, "3");
+    assert_eq!(cached_solution.input_hash, None);
+}
+
+#[test]
+fn load_missing_file() {
+    assert!(CachedSolution::load(scratch_path("missing")).is_err());
+    assert_eq!(
+        CachedSolution::load_if_exists(scratch_path("missing")).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn load_corrupt_json() {
+    let filepath = scratch_path("corrupt");
+    std::fs::write(&filepath, r#"{"answer": "3""#).unwrap();
+    let cached_solution = CachedSolution::load_if_exists(&filepath);
+    std::fs::remove_file(&filepath).unwrap();
+
+    assert_eq!(cached_solution.unwrap_err().kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn matches_input() {
+    let cached_solution = CachedSolution::new("3", &fingerprint(b"L68\n"), "0123456789ab");
+    assert!(cached_solution.matches_input(&fingerprint(b"L68\n")));
+    assert!(!cached_solution.matches_input(&fingerprint(b"R68\n")));
+}
+
+#[test]
+fn legacy_solution_matches_any_input() {
+    let cached_solution = load("legacy-matches", "3");
+    assert!(cached_solution.matches_input(&fingerprint(b"L68\n")));
+    assert!(cached_solution.matches_input(&fingerprint(b"R68\n")));
+}
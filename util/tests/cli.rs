@@ -0,0 +1,61 @@
+//! Tests for parsing solution binaries' command lines.
+
+use std::ffi::OsString;
+
+use util::cli::{Command, SolutionCli, clap::Parser};
+
+#[test]
+fn log_file_without_path_is_followed_by_command() {
+    let cli = SolutionCli::try_parse_from(["day1-1", "--log-file", "history", "--input", "test"])
+        .unwrap();
+    let Command::History(history_args) = cli.command() else {
+        panic!("expected the history command");
+    };
+    assert_eq!(history_args.input.as_deref(), Some("test"));
+}
+
+#[test]
+fn log_file_with_path() {
+    assert!(SolutionCli::try_parse_from(["day1-1", "--log-file=day1.log", "history"]).is_ok());
+}
+
+#[test]
+fn watch_passes_on_options() {
+    let cli = SolutionCli::try_parse_from([
+        "day1-1",
+        "--watch",
+        "--input",
+        "test",
+        "--normalize",
+        "-vv",
+        "--log=day1=trace",
+        "--log-file",
+        "--timings",
+    ])
+    .unwrap();
+    let Command::Solve(solve_args) = cli.command() else {
+        panic!("expected the solve command");
+    };
+    let expected: Vec<OsString> = [
+        "--normalize",
+        "--verbose",
+        "--verbose",
+        "--log=day1=trace",
+        "--log-file",
+        "--timings",
+    ]
+    .map(OsString::from)
+    .into();
+    assert_eq!(cli.watch_args(&solve_args).unwrap(), expected);
+}
+
+#[test]
+fn watch_passes_on_absolute_paths() {
+    let cli = SolutionCli::try_parse_from(["day1-1", "--watch", "--log-file=day1.log"]).unwrap();
+    let Command::Solve(solve_args) = cli.command() else {
+        panic!("expected the solve command");
+    };
+    let mut expected = OsString::from("--log-file=");
+    expected.push(std::env::current_dir().unwrap().join("day1.log"));
+    assert_eq!(cli.watch_args(&solve_args).unwrap(), [expected]);
+}
@@ -0,0 +1,19 @@
+//! Tests for graph searches which the doc examples don't cover.
+
+use util::graph::astar;
+
+/// With a heuristic which is admissible but not consistent, A* first reaches `A` directly, then
+/// finds a cheaper path to it through `B`, and has to expand it again to find the shortest path.
+#[test]
+fn astar_inconsistent_heuristic() {
+    let neighbors = |node: &char| match node {
+        'S' => vec![('A', 4), ('B', 1)],
+        'B' => vec![('A', 1)],
+        'A' => vec![('G', 4)],
+        _ => vec![],
+    };
+    let heuristic = |node: &char| if *node == 'B' { 5 } else { 0 };
+    let (path, cost) = astar(['S'], neighbors, heuristic, |node| *node == 'G').unwrap();
+    assert_eq!(path, ['S', 'B', 'A', 'G']);
+    assert_eq!(cost, 6);
+}
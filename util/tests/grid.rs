@@ -0,0 +1,87 @@
+//! Tests for [`Grid`]'s transforms and the lines of cells it can iterate over.
+
+use util::grid::{Adjacency, Grid, Point};
+
+/// A 2x3 grid:
+/// ```text
+/// abc
+/// def
+/// ```
+fn grid() -> Grid<char> {
+    Grid::parse("abc\ndef", Some).unwrap()
+}
+
+fn text(grid: &Grid<char>) -> String {
+    grid.display(|c| *c).to_string()
+}
+
+#[test]
+fn transpose() {
+    let transposed = grid().transpose();
+    assert_eq!((transposed.rows(), transposed.cols()), (3, 2));
+    assert_eq!(text(&transposed), "ad\nbe\ncf\n");
+    assert_eq!(transposed.transpose(), grid());
+}
+
+#[test]
+fn flip_horizontal() {
+    assert_eq!(text(&grid().flip_horizontal()), "cba\nfed\n");
+    assert_eq!(grid().flip_horizontal().flip_horizontal(), grid());
+}
+
+#[test]
+fn flip_vertical() {
+    assert_eq!(text(&grid().flip_vertical()), "def\nabc\n");
+    assert_eq!(grid().flip_vertical().flip_vertical(), grid());
+}
+
+#[test]
+fn rotate_clockwise() {
+    assert_eq!(text(&grid().rotate_clockwise()), "da\neb\nfc\n");
+}
+
+#[test]
+fn rotate_counterclockwise() {
+    let rotated = grid().rotate_counterclockwise();
+    assert_eq!((rotated.rows(), rotated.cols()), (3, 2));
+    assert_eq!(text(&rotated), "cf\nbe\nad\n");
+    assert_eq!(rotated.rotate_clockwise(), grid());
+
+    let full_turn = (0..4).fold(grid(), |grid, _| grid.rotate_counterclockwise());
+    assert_eq!(full_turn, grid());
+}
+
+#[test]
+fn diagonals() {
+    let grid = Grid::parse("abc\ndef\nghi", Some).unwrap();
+    assert_eq!(grid.diagonal((0, 0)).collect::<String>(), "aei");
+    assert_eq!(grid.diagonal((0, 1)).collect::<String>(), "bf");
+    assert_eq!(grid.diagonal((2, 2)).collect::<String>(), "i");
+    assert_eq!(grid.anti_diagonal((0, 2)).collect::<String>(), "ceg");
+    assert_eq!(grid.diagonal((3, 0)).count(), 0);
+}
+
+#[test]
+fn ray() {
+    let grid = grid();
+    let ray: Vec<_> = grid.ray((1, 2), Adjacency::Left).collect();
+    assert_eq!(
+        ray,
+        [
+            (Point::new(1, 2), &'f'),
+            (Point::new(1, 1), &'e'),
+            (Point::new(1, 0), &'d'),
+        ]
+    );
+    assert_eq!(grid.ray((0, 1), Adjacency::Top).count(), 1);
+    assert_eq!(grid.ray((0, 3), Adjacency::Left).count(), 0);
+}
+
+#[test]
+fn rows_and_columns() {
+    let grid = grid();
+    assert_eq!(grid.row(1), ['d', 'e', 'f']);
+    assert_eq!(grid.column(2).collect::<String>(), "cf");
+    assert!(grid.row(2).is_empty());
+    assert_eq!(grid.column(3).count(), 0);
+}
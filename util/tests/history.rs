@@ -0,0 +1,111 @@
+//! Tests for reading and summarizing `dayX/history.jsonl`.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use util::history::{self, HistoryRecord};
+use util::runner::Verdict;
+
+/// A path in the temporary directory that no other test uses.
+fn scratch_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("util-history-{}-{name}.jsonl", std::process::id()))
+}
+
+/// Write `contents` to a scratch file and load it as a history file.
+fn load(name: &str, contents: &str) -> Vec<HistoryRecord> {
+    let filepath = scratch_path(name);
+    std::fs::write(&filepath, contents).unwrap();
+    let records = history::load(&filepath);
+    std::fs::remove_file(&filepath).unwrap();
+    records.unwrap()
+}
+
+fn record(problem: &str, commit: &str, answer: &str, duration_secs: f64) -> HistoryRecord {
+    HistoryRecord {
+        timestamp: 0,
+        problem: problem.into(),
+        input: None,
+        answer: answer.into(),
+        duration_secs,
+        verdict: Verdict::Unverified,
+        commit: Some(commit.into()),
+    }
+}
+
+#[test]
+fn append_and_load() {
+    let filepath = scratch_path("round-trip");
+    history::append(&filepath, &record("day1-1", "8c91fff", "3", 0.5)).unwrap();
+    history::append(&filepath, &record("day1-2", "8c91fff", "6", 0.25)).unwrap();
+    let records = history::load(&filepath);
+    std::fs::remove_file(&filepath).unwrap();
+
+    let records = records.unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].problem, "day1-1");
+    assert_eq!(records[1].answer, "6");
+    assert_eq!(records[1].duration(), Duration::from_millis(250));
+}
+
+#[test]
+fn load_missing_file() {
+    assert!(history::load(scratch_path("missing")).unwrap().is_empty());
+}
+
+#[test]
+fn load_empty_file() {
+    assert!(load("empty", "").is_empty());
+}
+
+#[test]
+fn load_skips_malformed_lines() {
+    let valid = serde_json::to_string(&record("day1-1", "8c91fff", "3", 0.5)).unwrap();
+    let records = load(
+        "malformed",
+        &format!("{valid}\nnot json\n{{\"answer\": \"3\"}}\n{valid}\n"),
+    );
+    assert_eq!(records.len(), 2);
+}
+
+#[test]
+fn summarize_empty_history() {
+    assert!(history::summarize(&[], "day1-1", &None).is_empty());
+}
+
+#[test]
+fn summarize_collapses_runs() {
+    let records = [
+        record("day1-1", "8c91fff", "3", 0.5),
+        record("day1-2", "8c91fff", "6", 0.1),
+        record("day1-1", "8c91fff", "3", 0.25),
+        record("day1-1", "8c91fff", "4", 0.75),
+        record("day1-1", "a1b2c3d", "4", 1.0),
+    ];
+    let entries = history::summarize(&records, "day1-1", &None);
+
+    let summary: Vec<_> = entries
+        .iter()
+        .map(|entry| (entry.commit, entry.answer, entry.runs))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (Some("8c91fff"), "3", 2),
+            (Some("8c91fff"), "4", 1),
+            (Some("a1b2c3d"), "4", 1),
+        ]
+    );
+    assert_eq!(entries[0].fastest, Duration::from_millis(250));
+    assert_eq!(entries[0].slowest, Duration::from_millis(500));
+}
+
+#[test]
+fn summarize_filters_by_input() {
+    let mut test_record = record("day1-1", "8c91fff", "3", 0.5);
+    test_record.input = Some("test".into());
+    let records = [test_record, record("day1-1", "8c91fff", "1100", 0.5)];
+
+    let entries = history::summarize(&records, "day1-1", &Some("test".into()));
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].answer, "3");
+}
@@ -0,0 +1,96 @@
+//! Property tests comparing [`IntervalSet`] against a naive [`BTreeSet`] of every value.
+//!
+//! `u8` keeps the model small enough to check every value, including in complements.
+
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+
+use proptest::prelude::*;
+use util::range::IntervalSet;
+
+fn range() -> impl Strategy<Value = RangeInclusive<u8>> {
+    (any::<u8>(), any::<u8>()).prop_map(|(a, b)| a.min(b)..=a.max(b))
+}
+
+fn ranges() -> impl Strategy<Value = Vec<RangeInclusive<u8>>> {
+    prop::collection::vec(range(), 0..12)
+}
+
+fn model(ranges: &[RangeInclusive<u8>]) -> BTreeSet<u8> {
+    ranges.iter().flat_map(|range| range.clone()).collect()
+}
+
+/// Check that `set` holds exactly the values in `model`, and that its ranges are sorted, disjoint
+/// and don't touch.
+fn assert_matches(set: &IntervalSet<u8>, model: &BTreeSet<u8>) {
+    for value in u8::MIN..=u8::MAX {
+        assert_eq!(set.contains(value), model.contains(&value), "{value}");
+    }
+    assert_eq!(set.len(), model.len() as u128);
+    assert_eq!(set.is_empty(), model.is_empty());
+    assert_eq!(set.values().collect::<BTreeSet<_>>(), *model);
+
+    let ranges: Vec<_> = set.ranges().collect();
+    for range in ranges.iter() {
+        assert!(range.start() <= range.end(), "{ranges:?}");
+    }
+    for pair in ranges.windows(2) {
+        assert!(
+            u16::from(*pair[0].end()) + 1 < u16::from(*pair[1].start()),
+            "{ranges:?}"
+        );
+    }
+}
+
+proptest! {
+    #[test]
+    fn insert(ranges in ranges()) {
+        let set: IntervalSet<u8> = ranges.iter().cloned().collect();
+        assert_matches(&set, &model(&ranges));
+    }
+
+    #[test]
+    fn remove(inserted in ranges(), removed in ranges()) {
+        let mut set: IntervalSet<u8> = inserted.iter().cloned().collect();
+        let mut expected = model(&inserted);
+        for range in removed {
+            set.remove(range.clone());
+            for value in range {
+                expected.remove(&value);
+            }
+            assert_matches(&set, &expected);
+        }
+    }
+
+    #[test]
+    fn set_operations(a in ranges(), b in ranges()) {
+        let (set_a, set_b): (IntervalSet<u8>, IntervalSet<u8>) =
+            (a.iter().cloned().collect(), b.iter().cloned().collect());
+        let (model_a, model_b) = (model(&a), model(&b));
+
+        assert_matches(&set_a.union(&set_b), &(&model_a | &model_b));
+        assert_matches(&set_a.intersection(&set_b), &(&model_a & &model_b));
+        assert_matches(&set_a.difference(&set_b), &(&model_a - &model_b));
+
+        let everything: BTreeSet<u8> = (u8::MIN..=u8::MAX).collect();
+        assert_matches(&set_a.complement(), &(&everything - &model_a));
+    }
+}
+
+#[test]
+fn extremes() {
+    let mut set = IntervalSet::<u8>::full();
+    assert_eq!(set.len(), 256);
+    assert!(set.complement().is_empty());
+
+    set.remove(0..=0);
+    set.remove(255..=255);
+    assert_eq!(set.ranges().collect::<Vec<_>>(), vec![1..=254]);
+    assert_eq!(
+        set.complement().ranges().collect::<Vec<_>>(),
+        vec![0..=0, 255..=255]
+    );
+
+    let full = IntervalSet::<u128>::full();
+    assert_eq!(full.len(), u128::MAX);
+}
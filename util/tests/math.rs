@@ -0,0 +1,228 @@
+//! Checks `util::math` against brute force over small inputs, for several integer types.
+
+use util::math::{
+    checked_pow10, crt, digit_count, extended_gcd, gcd, isqrt, lcm, mod_inverse, mod_pow, wrap,
+};
+
+/// Run a test body once for each listed integer type, with `T` bound to the type.
+macro_rules! for_types {
+    ($($t:ty),* => $body:block) => {
+        $({
+            #[allow(dead_code)]
+            type T = $t;
+            $body
+        })*
+    };
+}
+
+#[test]
+fn gcd_and_lcm() {
+    for_types!(u8, u32, u64, u128, i16, i64, i128 => {
+        for a in 0..=15 as T {
+            for b in 0..=15 as T {
+                let brute_gcd = (1..=a.max(b)).rev().find(|d| a % d == 0 && b % d == 0).unwrap_or(0);
+                assert_eq!(gcd(a, b), Some(brute_gcd), "gcd({a}, {b})");
+
+                let brute_lcm = if a == 0 || b == 0 {
+                    0
+                } else {
+                    (1..=a * b).find(|m| m % a == 0 && m % b == 0).unwrap()
+                };
+                assert_eq!(lcm(a, b), Some(brute_lcm), "lcm({a}, {b})");
+            }
+        }
+    });
+    // The type's bounds, where negating or taking a remainder of `MIN` overflows.
+    for_types!(u8, u64, u128, i8, i32, i128 => {
+        assert_eq!(gcd(T::MAX, T::MAX), Some(T::MAX));
+        assert_eq!(gcd(T::MAX, T::MAX - 1), Some(1));
+        assert_eq!(lcm(T::MAX, 1), Some(T::MAX));
+        assert_eq!(lcm(T::MAX, 2), None);
+    });
+    for_types!(i8, i32, i128 => {
+        assert_eq!(gcd(T::MIN, -1), Some(1));
+        assert_eq!(gcd(T::MIN, T::MAX), Some(1));
+        assert_eq!(gcd(T::MIN, T::MIN / 2), Some(-(T::MIN / 2)));
+        assert_eq!(gcd(T::MIN, 0), None);
+        assert_eq!(gcd(T::MIN, T::MIN), None);
+        assert_eq!(lcm(T::MIN / 2, 2), Some(-(T::MIN / 2)));
+        assert_eq!(lcm(T::MIN, -1), None);
+        assert_eq!(lcm(T::MIN, 0), Some(0));
+    });
+    assert_eq!(gcd(-12i32, -18), Some(6));
+    assert_eq!(lcm(-4i32, 6), Some(12));
+    assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+}
+
+#[test]
+fn extended_gcd_coefficients() {
+    for_types!(i8, i32, i128 => {
+        for a in -11..=11 as T {
+            for b in -11..=11 as T {
+                let (g, x, y) = extended_gcd(a, b).unwrap();
+                assert_eq!(Some(g), gcd(a, b), "extended_gcd({a}, {b})");
+                assert_eq!(a * x + b * y, g, "extended_gcd({a}, {b})");
+            }
+        }
+    });
+    // The type's bounds, where `a * x` can overflow even though `a * x + b * y` doesn't, so the
+    // sum is checked with wrapping arithmetic and the coefficients are checked to be minimal.
+    for_types!(i8, i32, i128 => {
+        let values = [T::MIN, T::MIN + 1, T::MIN / 2, -3, -1, 0, 1, 2, T::MAX / 2, T::MAX - 1, T::MAX];
+        for a in values {
+            for b in values {
+                let Some(g) = gcd(a, b) else {
+                    assert_eq!(extended_gcd(a, b), None, "extended_gcd({a}, {b})");
+                    continue;
+                };
+                let (extended_g, x, y) = extended_gcd(a, b).unwrap();
+                assert_eq!(extended_g, g, "extended_gcd({a}, {b})");
+                assert_eq!(
+                    a.wrapping_mul(x).wrapping_add(b.wrapping_mul(y)),
+                    g,
+                    "extended_gcd({a}, {b})"
+                );
+                if g != 0 {
+                    assert!(x.unsigned_abs() <= b.unsigned_abs() / g.unsigned_abs() || b == 0);
+                    assert!(y.unsigned_abs() <= a.unsigned_abs() / g.unsigned_abs() || a == 0);
+                }
+            }
+        }
+    });
+}
+
+#[test]
+fn mod_inverse_and_pow() {
+    for_types!(u8, u16, u64, u128, i8, i64, i128 => {
+        for modulus in 1..=11 as T {
+            for a in 0..11 as T {
+                let brute_inverse = (0..modulus).find(|x| a * x % modulus == 1 % modulus);
+                assert_eq!(mod_inverse(a, modulus), brute_inverse, "{a}^-1 mod {modulus}");
+
+                for exp in 0..6u64 {
+                    let brute_pow = (0..exp).fold(1 % modulus, |power, _| power * a % modulus);
+                    assert_eq!(mod_pow(a, exp, modulus), brute_pow, "{a}^{exp} mod {modulus}");
+                }
+            }
+        }
+    });
+    for a in -20..=20i32 {
+        for modulus in 1..=20i32 {
+            let brute_inverse = (0..modulus).find(|x| (a * x).rem_euclid(modulus) == 1 % modulus);
+            assert_eq!(
+                mod_inverse(a, modulus),
+                brute_inverse,
+                "{a}^-1 mod {modulus}"
+            );
+            assert_eq!(mod_pow(a, 3, modulus), (a * a * a).rem_euclid(modulus));
+        }
+    }
+    // Products which overflow the type itself.
+    assert_eq!(mod_pow(u64::MAX - 1, 2, u64::MAX), 1);
+    assert_eq!(mod_pow(i128::MAX - 1, 2, i128::MAX), 1);
+}
+
+#[test]
+fn chinese_remainder_theorem() {
+    for_types!(u16, u64, i32, i128 => {
+        for m1 in 1..=8 as T {
+            for m2 in 1..=8 as T {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let brute_lcm = lcm(m1, m2).unwrap();
+                        let brute = (0..brute_lcm).find(|x| x % m1 == r1 && x % m2 == r2);
+                        assert_eq!(
+                            crt(&[(r1, m1), (r2, m2)]),
+                            brute.map(|x| (x, brute_lcm)),
+                            "x = {r1} mod {m1}, x = {r2} mod {m2}"
+                        );
+                    }
+                }
+            }
+        }
+    });
+    assert_eq!(crt::<u32>(&[]), Some((0, 1)));
+    assert_eq!(crt(&[(-1i64, 5), (-1, 7)]), Some((34, 35)));
+}
+
+#[test]
+fn wrapping() {
+    for_types!(u8, u64, i8, i32, i128 => {
+        for start in 0..=5 as T {
+            for end in start + 1..=start + 7 {
+                for value in 0..=60 as T {
+                    let mut brute = value;
+                    while brute >= end {
+                        brute -= end - start;
+                    }
+                    while brute < start {
+                        brute += end - start;
+                    }
+                    assert_eq!(wrap(value, start..end), brute, "wrap({value}, {start}..{end})");
+                }
+            }
+        }
+    });
+    for_types!(i8, i32, i128 => {
+        for start in -5..=5 as T {
+            for end in start + 1..=start + 7 {
+                for value in -40..=40 as T {
+                    let mut brute = value;
+                    while brute < start {
+                        brute += end - start;
+                    }
+                    while brute >= end {
+                        brute -= end - start;
+                    }
+                    assert_eq!(wrap(value, start..end), brute, "wrap({value}, {start}..{end})");
+                }
+            }
+        }
+    });
+    // Values and ranges at the type's bounds, where naive differences overflow.
+    for_types!(u8, u64, i8, i32, i128 => {
+        let mid = T::MIN / 2 + T::MAX / 2;
+        let ranges = [
+            T::MIN..T::MAX,
+            T::MIN..T::MIN + 1,
+            T::MAX - 1..T::MAX,
+            T::MIN..mid,
+            mid..T::MAX,
+            mid - 1..mid + 2,
+        ];
+        for range in ranges {
+            for value in [T::MIN, T::MIN + 1, mid, T::MAX - 1, T::MAX] {
+                let wrapped = wrap(value, range.clone());
+                assert!(range.contains(&wrapped), "wrap({value}, {range:?}) = {wrapped}");
+                // `value` and `wrapped` differ by a multiple of the range's length.
+                let len = range.end.abs_diff(range.start);
+                assert_eq!(value.abs_diff(wrapped) % len, 0, "wrap({value}, {range:?}) = {wrapped}");
+            }
+        }
+    });
+    assert_eq!(wrap(i64::MIN, 0..100), 92);
+    assert_eq!(wrap(99, -100i8..100), 99);
+    assert_eq!(wrap(-128, -100i8..100), 72);
+}
+
+#[test]
+fn square_roots_and_digits() {
+    for_types!(u8, u32, u128, i16, i64 => {
+        for n in 0..=120 as T {
+            let brute_sqrt = (0..=n).take_while(|root| root * root <= n).last().unwrap();
+            assert_eq!(isqrt(n), brute_sqrt, "isqrt({n})");
+            assert_eq!(digit_count(n) as usize, n.to_string().len(), "digit_count({n})");
+        }
+        assert_eq!(digit_count(T::MAX) as usize, T::MAX.to_string().len());
+        assert_eq!(
+            digit_count(T::MIN) as usize,
+            T::MIN.to_string().trim_start_matches('-').len()
+        );
+    });
+    for_types!(u8, u16, u32, u64, u128, i8, i32, i128 => {
+        for exp in 0..45u32 {
+            let brute = 10u128.checked_pow(exp).and_then(|power| T::try_from(power).ok());
+            assert_eq!(checked_pow10::<T>(exp), brute, "10^{exp}");
+        }
+    });
+}
@@ -0,0 +1,122 @@
+//! Tests for [`ParseRange`] and [`ParseOpenRange`] beyond the examples in their docs.
+
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use util::range::{ParseOpenRange, ParseRange, RangeError, RangeSeparator};
+
+/// A type which only implements [`FromStr`], like most newtypes in solutions.
+#[derive(Debug, PartialEq)]
+struct Id(u32);
+
+impl FromStr for Id {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.strip_prefix('#').unwrap_or(s).parse().map(Id)
+    }
+}
+
+#[test]
+fn bounded_newtype() {
+    assert_eq!(<(Id, Id)>::parse_range("#3-#17"), Ok((Id(3), Id(17))));
+    assert_eq!(
+        RangeInclusive::<Id>::parse_range_with("#3 to 17", RangeSeparator::To),
+        Ok(Id(3)..=Id(17))
+    );
+}
+
+#[test]
+fn bounded_floats() {
+    assert_eq!(
+        <(f32, f32)>::parse_range_with("-1.5,2.25", RangeSeparator::Comma),
+        Ok((-1.5, 2.25))
+    );
+    assert_eq!(
+        RangeInclusive::<f64>::parse_range("1e3-2e3"),
+        Ok(1000.0..=2000.0)
+    );
+}
+
+#[test]
+fn bounded_requires_both_sides() {
+    assert_eq!(
+        RangeInclusive::<u64>::parse_range("5-"),
+        Err(RangeError::InvalidEnd {
+            range: "5-".into(),
+            end: "".into()
+        })
+    );
+    assert_eq!(
+        <(char, char)>::parse_range_with("..=z", RangeSeparator::DotsEq),
+        Err(RangeError::InvalidStart {
+            range: "..=z".into(),
+            start: "".into()
+        })
+    );
+}
+
+#[test]
+fn bounded_rejects_exclusive_end() {
+    assert_eq!(
+        RangeInclusive::<u64>::parse_range_with("3..5", RangeSeparator::Dots),
+        Err(RangeError::ExclusiveEnd("3..5".into()))
+    );
+}
+
+#[test]
+fn missing_separator() {
+    assert_eq!(
+        RangeInclusive::<u64>::parse_range("35"),
+        Err(RangeError::MissingSeparator {
+            range: "35".into(),
+            separator: "-"
+        })
+    );
+}
+
+#[test]
+fn open_sides() {
+    let parse = |s, separator| <(i8, i8)>::parse_open_range_with(s, separator);
+    assert_eq!(parse("-", RangeSeparator::Hyphen), Err(missing_hyphen("-")));
+    assert_eq!(parse("..", RangeSeparator::Dots), Ok((i8::MIN, i8::MAX)));
+    assert_eq!(parse("..=", RangeSeparator::DotsEq), Ok((i8::MIN, i8::MAX)));
+    assert_eq!(parse("-3..", RangeSeparator::Dots), Ok((-3, i8::MAX)));
+    assert_eq!(parse(",-3", RangeSeparator::Comma), Ok((i8::MIN, -3)));
+    assert_eq!(parse("-3-", RangeSeparator::Hyphen), Ok((-3, i8::MAX)));
+}
+
+#[test]
+fn open_hyphen_reads_leading_minus_as_sign() {
+    assert_eq!(
+        RangeInclusive::<i32>::parse_open_range("-5"),
+        Err(missing_hyphen("-5"))
+    );
+    assert_eq!(
+        RangeInclusive::<i32>::parse_open_range_with("..=-5", RangeSeparator::DotsEq),
+        Ok(i32::MIN..=-5)
+    );
+}
+
+#[test]
+fn open_exclusive_end() {
+    let parse = |s| RangeInclusive::<u8>::parse_open_range_with(s, RangeSeparator::Dots);
+    assert_eq!(parse("3..4"), Ok(3..=3));
+    assert_eq!(parse("0..0"), Err(RangeError::Empty("0..0".into())));
+    assert_eq!(parse("..0"), Err(RangeError::Empty("..0".into())));
+    // `..=` is a different separator, not `..` followed by `=5`.
+    assert_eq!(
+        parse("3..=5"),
+        Err(RangeError::MissingSeparator {
+            range: "3..=5".into(),
+            separator: ".."
+        })
+    );
+}
+
+fn missing_hyphen(range: &str) -> RangeError {
+    RangeError::MissingSeparator {
+        range: range.into(),
+        separator: "-",
+    }
+}
@@ -0,0 +1,35 @@
+//! Tests for `#[derive(AocParse)]` beyond the examples in its docs.
+
+use util::scan::AocParse;
+
+#[derive(AocParse, Debug, PartialEq)]
+enum Expression {
+    #[aoc(pattern = "{} + {}")]
+    Add(u32, u32),
+    #[aoc(pattern = "{} * {}")]
+    Multiply(u32, u32),
+}
+
+#[derive(AocParse, Debug, PartialEq)]
+enum Command {
+    #[aoc(pattern = "move {} to {}")]
+    MoveTo(u32, u32),
+    #[aoc(pattern = "move {} by {}")]
+    MoveBy(u32, u32),
+}
+
+#[test]
+fn variants_without_prefix_are_tried_in_turn() {
+    assert_eq!("1 + 2".parse(), Ok(Expression::Add(1, 2)));
+    assert_eq!("1 * 2".parse(), Ok(Expression::Multiply(1, 2)));
+
+    let error = "1 - 2".parse::<Expression>().unwrap_err();
+    assert_eq!(error.message, r#"expected " + " after field"#);
+}
+
+#[test]
+fn variants_with_shared_prefix_are_tried_in_turn() {
+    assert_eq!("move 1 to 2".parse(), Ok(Command::MoveTo(1, 2)));
+    assert_eq!("move 1 by 2".parse(), Ok(Command::MoveBy(1, 2)));
+    assert!("move 1 from 2".parse::<Command>().is_err());
+}
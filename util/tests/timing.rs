@@ -0,0 +1,45 @@
+//! Tests for [`SpanTimingLayer`] alongside other layers.
+
+use std::sync::{Arc, Mutex};
+
+use tracing::Subscriber;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::prelude::*;
+use util::timing::SpanTimingLayer;
+
+/// A [`Layer`] which keeps the name of every event it sees.
+#[derive(Clone, Default)]
+struct EventNames(Arc<Mutex<Vec<String>>>);
+
+impl<S: Subscriber> Layer<S> for EventNames {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        self.0.lock().unwrap().push(event.metadata().name().into());
+    }
+}
+
+#[test]
+fn events_reach_other_layers() {
+    let events = EventNames::default();
+    let (timing_layer, timings) = SpanTimingLayer::new(false);
+    // Mirrors `util::logging::init`, where the log layers are filtered but the timing layer isn't.
+    let subscriber = tracing_subscriber::registry()
+        .with(events.clone().with_filter(LevelFilter::INFO))
+        .with(timing_layer);
+
+    tracing::subscriber::with_default(subscriber, || {
+        let _span = tracing::info_span!("solve").entered();
+        tracing::info!(name: "inside", "inside a span");
+        tracing::debug!(name: "filtered", "below the log level");
+    });
+
+    assert_eq!(*events.0.lock().unwrap(), ["inside"]);
+
+    let mut summary = vec![];
+    timings.print_summary(&mut summary).unwrap();
+    let summary = String::from_utf8(summary).unwrap();
+    assert!(
+        summary.lines().any(|line| line.starts_with("solve ")),
+        "{summary}"
+    );
+}